@@ -42,7 +42,8 @@
 //! Calling a function that has not been loaded will result in a failure like:
 //! `panic!("gl::Viewport was not loaded")`, which avoids a segfault. This feature
 //! does not cause any run time overhead because the failing functions are
-//! assigned only when `load_with` is called.
+//! assigned only when `load_with` is called. The panic unwinds through the
+//! function pointer, so the bindings need Rust 1.71 or newer.
 //!
 //! All OpenGL function calls are `unsafe`.
 //!
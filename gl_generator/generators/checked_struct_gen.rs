@@ -0,0 +1,514 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::{Enum, Registry};
+use std::collections::BTreeMap;
+use std::io;
+use Api;
+use GeneratorOptions;
+
+/// Like `StructGenerator`, but each command checks `glGetError` after it is called, and returns
+/// `Result<T, GlError>`.
+#[allow(missing_copy_implementations)]
+pub struct CheckedStructGenerator;
+
+impl super::Generator for CheckedStructGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(registry, dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_error_enum(registry, dest)?;
+        write_fnptr_struct_def(dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        write_struct(registry, dest)?;
+        write_impl(registry, options, dest)?;
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            {error_imports}
+            pub use std::mem;
+            pub use std::marker::Send;
+            pub use std::os::raw;
+        }}
+    "#,
+        error_imports = if has_get_error(registry) {
+            "pub use std::error; pub use std::fmt;"
+        } else {
+            ""
+        },
+    )
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the errors of the bindings can be checked with `glGetError`.
+///
+/// EGL has an `eglGetError`, but it reports the errors of the EGL calls on the current thread
+/// and is not checked by the wrappers.
+fn has_get_error(registry: &Registry) -> bool {
+    registry.api != Api::Egl
+        && registry
+            .cmds
+            .iter()
+            .any(|cmd| cmd.proto.ident == "GetError")
+}
+
+/// Returns `true` if `glGetError` can't be called right after the command, because the command
+/// is `glBegin` or one of the commands that can be called between `glBegin` and `glEnd`.
+///
+/// The errors raised by these commands are returned by the next checked command, usually `glEnd`.
+///
+/// The registry doesn't say which commands are allowed between `glBegin` and `glEnd`, so `EXACT`
+/// and `PREFIXES` follow the list of the specification, and `TYPE_SUFFIXES` match the variants of
+/// each of them, such as `Color4ubv` for `Color`.
+fn is_unchecked(ident: &str) -> bool {
+    const EXACT: &[&str] = &["Begin", "ArrayElement", "CallList", "CallLists"];
+    const PREFIXES: &[&str] = &[
+        "Color",
+        "ColorP",
+        "EdgeFlag",
+        "EvalCoord",
+        "EvalPoint",
+        "FogCoord",
+        "Index",
+        "Material",
+        "MultiTexCoord",
+        "MultiTexCoordP",
+        "Normal",
+        "NormalP",
+        "SecondaryColor",
+        "SecondaryColorP",
+        "TexCoord",
+        "TexCoordP",
+        "Vertex",
+        "VertexAttrib",
+        "VertexAttribI",
+        "VertexAttribL",
+        "VertexAttribP",
+        "VertexP",
+    ];
+    // what may follow the prefix, after the number of components and before the vendor suffix
+    const TYPE_SUFFIXES: &[&str] = &[
+        "", "b", "s", "i", "f", "d", "h", "x", "ub", "us", "ui", "i64", "ui64", "v", "bv", "sv",
+        "iv", "fv", "dv", "hv", "xv", "ubv", "usv", "uiv", "i64v", "ui64v",
+    ];
+
+    if EXACT.contains(&ident.trim_end_matches(char::is_uppercase)) {
+        return true;
+    }
+    PREFIXES.iter().any(|prefix| {
+        let rest = match ident.strip_prefix(prefix) {
+            Some(rest) => rest,
+            None => return false,
+        };
+        let rest = rest
+            .trim_end_matches(char::is_uppercase)
+            .trim_start_matches(|c: char| c.is_ascii_digit());
+        let rest = rest.strip_prefix('N').unwrap_or(rest);
+        TYPE_SUFFIXES.contains(&rest)
+    })
+}
+
+/// Creates a `GlError` enum with a variant for each error code of the `ErrorCode` group.
+fn write_error_enum<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !has_get_error(registry) {
+        return Ok(());
+    }
+
+    // some error codes are also provided by extensions, under another name
+    let mut codes: BTreeMap<u64, &Enum> = BTreeMap::new();
+    if let Some(group) = registry.groups.get("ErrorCode") {
+        for enm in registry
+            .enums
+            .iter()
+            .filter(|e| group.enums.contains(&e.ident))
+        {
+            let code = match super::parse_enum_value(&enm.value) {
+                Some(0) | None => continue,
+                Some(code) => code,
+            };
+            let rank = |e: &Enum| (e.alias.is_some(), e.introduced_in.is_none());
+            match codes.get(&code) {
+                Some(other) if rank(other) <= rank(enm) => continue,
+                _ => {},
+            }
+            codes.insert(code, enm);
+        }
+    }
+    let variants: Vec<(u64, &str, String)> = codes
+        .iter()
+        .map(|(&code, enm)| (code, &enm.ident[..], gen_variant_name(&enm.ident)))
+        .collect();
+
+    writeln!(
+        dest,
+        "/// An error reported by `glGetError`.
+        #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum GlError {{"
+    )?;
+    for &(_, ident, ref variant) in &variants {
+        writeln!(dest, "/// `{}`\n{},", ident, variant)?;
+    }
+    writeln!(
+        dest,
+        "/// An error code that is not part of the `ErrorCode` group.
+            Unknown(types::GLenum),
+        }}
+
+        #[allow(dead_code)]
+        impl GlError {{
+            /// Returns the error for a value returned by `glGetError`, other than `NO_ERROR`.
+            pub fn from_code(code: types::GLenum) -> GlError {{
+                match code {{"
+    )?;
+    for &(code, _, ref variant) in &variants {
+        writeln!(dest, "{:#x} => GlError::{},", code, variant)?;
+    }
+    writeln!(
+        dest,
+        "code => GlError::Unknown(code),
+                }}
+            }}
+
+            /// Returns the value returned by `glGetError` for the error.
+            pub fn code(self) -> types::GLenum {{
+                match self {{"
+    )?;
+    for &(code, _, ref variant) in &variants {
+        writeln!(dest, "GlError::{} => {:#x},", variant, code)?;
+    }
+    writeln!(
+        dest,
+        "GlError::Unknown(code) => code,
+                }}
+            }}
+        }}
+
+        impl __gl_imports::fmt::Display for GlError {{
+            fn fmt(&self, f: &mut __gl_imports::fmt::Formatter) -> __gl_imports::fmt::Result {{
+                match *self {{"
+    )?;
+    for &(_, ident, ref variant) in &variants {
+        writeln!(dest, "GlError::{} => f.write_str(\"{}\"),", variant, ident)?;
+    }
+    writeln!(
+        dest,
+        "GlError::Unknown(code) => write!(f, \"unknown error {{:#x}}\", code),
+                }}
+            }}
+        }}
+
+        impl __gl_imports::error::Error for GlError {{}}"
+    )
+}
+
+/// Converts the name of an error code, such as `INVALID_ENUM`, to a variant name, such as
+/// `InvalidEnum`.
+fn gen_variant_name(ident: &str) -> String {
+    ident
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Creates a `FnPtr` structure which contains the store for a single binding.
+fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(dead_code, missing_copy_implementations)]
+        #[derive(Clone)]
+        pub struct FnPtr {{
+            /// The function pointer that will be used when calling the function.
+            f: *const __gl_imports::raw::c_void,
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
+            is_loaded: bool,
+        }}
+
+        impl FnPtr {{
+            /// Creates a `FnPtr` from a load attempt.
+            fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
+                if ptr.is_null() {{
+                    FnPtr {{ f: missing, is_loaded: false }}
+                }} else {{
+                    FnPtr {{ f: ptr, is_loaded: true }}
+                }}
+            }}
+
+            /// Returns `true` if the function has been successfully loaded.
+            ///
+            /// If it returns `false`, calling the corresponding function will fail.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn is_loaded(&self) -> bool {{
+                self.is_loaded
+            }}
+        }}
+    "
+    )
+}
+
+/// Creates a structure which stores all the `FnPtr` of the bindings.
+///
+/// The name of the struct corresponds to the namespace.
+fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        #[derive(Clone)]
+        pub struct {api} {{",
+        api = super::gen_struct_name(registry.api)
+    )?;
+
+    for cmd in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        writeln!(dest, "pub {name}: FnPtr,", name = cmd.proto.ident)?;
+    }
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `impl` of the structure created by `write_struct`.
+fn write_impl<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol using a custom load function. This allows for the
+            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                #[inline(never)]
+                fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                                 symbol: &'static str,
+                                 symbols: &[&'static str])
+                                 -> *const __gl_imports::raw::c_void {{
+                    let mut ptr = loadfn(symbol);
+                    if ptr.is_null() {{
+                        for &sym in symbols {{
+                            ptr = loadfn(sym);
+                            if !ptr.is_null() {{ break; }}
+                        }}
+                    }}
+                    ptr
+                }}
+                let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
+                    do_metaloadfn(&mut loadfn, symbol, symbols)
+                }};
+                {api} {{",
+                  api = super::gen_struct_name(registry.api))?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}]), \
+                missing_fns::{name} as *const __gl_imports::raw::c_void),",
+            name = cmd.proto.ident,
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks = match registry.aliases.get(&cmd.proto.ident) {
+                Some(fbs) => fbs
+                    .iter()
+                    .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, name)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                None => String::new(),
+            },
+        )?
+    }
+
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(
+        dest,
+        "}}
+        }}"
+    )?;
+
+    let checked = has_get_error(registry);
+    // without `glBegin`, as in core profiles and GLES, every command can be checked
+    let has_begin = registry.cmds.iter().any(|cmd| cmd.proto.ident == "Begin");
+    for cmd in &registry.cmds {
+        let unchecked = has_begin && is_unchecked(&cmd.proto.ident);
+        let call = format!(
+            "__gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                (fn_ptr)({idents})",
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+        );
+        let returns_result = checked && cmd.proto.ident != "GetError";
+        let check =
+            super::gen_missing_fn_check(cmd, options, &format!("self.{}.f", cmd.proto.ident));
+        let (return_suffix, body) = if !returns_result {
+            (cmd.proto.ty.to_string(), format!("{} {}", check, call))
+        } else if unchecked {
+            (
+                format!("Result<{}, GlError>", cmd.proto.ty),
+                format!("{} Ok({})", check, call),
+            )
+        } else {
+            (
+                format!("Result<{}, GlError>", cmd.proto.ty),
+                format!(
+                    "{check}
+                    let r = {call};
+                    match __gl_imports::mem::transmute::<_, extern \"{abi}\" fn() -> types::GLenum>\
+                        (self.GetError.f)() {{
+                        0 => Ok(r),
+                        code => Err(GlError::from_code(code)),
+                    }}",
+                    check = check,
+                    call = call,
+                    abi = super::gen_call_abi(options),
+                ),
+            )
+        };
+
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        if checked && unchecked {
+            writeln!(
+                dest,
+                "///\n/// The errors of this command are returned by the next checked command, usually `End`."
+            )?;
+        }
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+        writeln!(
+            dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline] {missing_fn_attr} pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ {body} }}",
+            name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            return_suffix = return_suffix,
+            body = body,
+        )?
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        unsafe impl __gl_imports::Send for {api} {{}}",
+        api = super::gen_struct_name(registry.api)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_unchecked;
+
+    #[test]
+    fn test_is_unchecked() {
+        assert!(is_unchecked("Begin"));
+        assert!(is_unchecked("EdgeFlag"));
+        assert!(is_unchecked("EdgeFlagv"));
+        assert!(is_unchecked("Color4ubv"));
+        assert!(is_unchecked("VertexAttrib4Nub"));
+        assert!(is_unchecked("MultiTexCoord2fARB"));
+        assert!(is_unchecked("NormalP3uiv"));
+
+        assert!(!is_unchecked("End"));
+        assert!(!is_unchecked("GetError"));
+        assert!(!is_unchecked("EdgeFlagPointer"));
+        assert!(!is_unchecked("ColorMask"));
+        assert!(!is_unchecked("VertexAttribPointer"));
+    }
+}
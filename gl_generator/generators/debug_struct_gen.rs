@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use registry::Registry;
+use registry::{Cmd, Registry};
 use std::io;
+use Api;
+use GeneratorOptions;
 
 #[allow(missing_copy_implementations)]
 pub struct DebugStructGenerator;
@@ -23,13 +25,34 @@ impl super::Generator for DebugStructGenerator {
     where
         W: io::Write,
     {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        // the calls are logged with the names of their enum arguments
+        let options = &GeneratorOptions {
+            enum_names: true,
+            ..options.clone()
+        };
+        let registry = &super::apply_options(registry, options);
         write_header(dest)?;
         write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_debug_enum(registry, dest)?;
         write_fnptr_struct_def(dest)?;
-        write_panicking_fns(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
         write_struct(registry, dest)?;
-        write_impl(registry, dest)?;
+        write_impl(registry, options, dest)?;
         Ok(())
     }
 }
@@ -44,9 +67,11 @@ where
         dest,
         r#"
         mod __gl_imports {{
+            pub use std::fmt;
             pub use std::mem;
             pub use std::marker::Send;
             pub use std::os::raw;
+            pub use std::sync::Arc;
         }}
     "#
     )
@@ -67,23 +92,77 @@ where
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry, dest)?;
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
         super::gen_enum_item(enm, "types::", dest)?;
     }
 
     Ok(())
 }
 
+/// Creates a `DebugEnum` structure, which formats an enum argument with the name of its value.
+///
+/// See also `generators::gen_enum_names`.
+fn write_debug_enum<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if registry.api == Api::Egl {
+        return Ok(());
+    }
+
+    writeln!(
+        dest,
+        "
+        /// An enum argument and the name of its group.
+        #[allow(dead_code)]
+        struct DebugEnum(&'static str, types::GLenum);
+
+        impl __gl_imports::fmt::Debug for DebugEnum {{
+            fn fmt(&self, f: &mut __gl_imports::fmt::Formatter) -> __gl_imports::fmt::Result {{
+                match enum_name_in(self.0, self.1) {{
+                    Some(name) => f.write_str(name),
+                    None => write!(f, \"{{:#x}}\", self.1),
+                }}
+            }}
+        }}
+    "
+    )
+}
+
+/// Returns the expression that is logged for each argument of `cmd`.
+///
+/// Arguments that belong to a group of enums are logged by name, if they are plain enums.
+fn gen_debug_args(registry: &Registry, cmd: &Cmd) -> Vec<String> {
+    cmd.params
+        .iter()
+        .map(|binding| {
+            let is_plain_enum = binding.ty == "types::GLenum" || binding.ty == "types::GLbitfield";
+            match binding.group {
+                _ if binding.ty.contains("GLDEBUGPROC") => "\"<callback>\"".to_string(),
+                Some(ref group)
+                    if is_plain_enum
+                        && registry.api != Api::Egl
+                        && registry.groups.contains_key(group) =>
+                {
+                    format!("DebugEnum(\"{}\", {})", group, binding.ident)
+                },
+                _ => binding.ident.clone(),
+            }
+        })
+        .collect()
+}
+
 /// Creates a `FnPtr` structure which contains the store for a single binding.
 fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
 where
@@ -97,18 +176,16 @@ where
         pub struct FnPtr {{
             /// The function pointer that will be used when calling the function.
             f: *const __gl_imports::raw::c_void,
-            /// True if the pointer points to a real function, false if points to a `panic!` fn.
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
             is_loaded: bool,
         }}
 
         impl FnPtr {{
             /// Creates a `FnPtr` from a load attempt.
-            fn new(ptr: *const __gl_imports::raw::c_void) -> FnPtr {{
+            fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
                 if ptr.is_null() {{
-                    FnPtr {{
-                        f: missing_fn_panic as *const __gl_imports::raw::c_void,
-                        is_loaded: false
-                    }}
+                    FnPtr {{ f: missing, is_loaded: false }}
                 }} else {{
                     FnPtr {{ f: ptr, is_loaded: true }}
                 }}
@@ -127,23 +204,6 @@ where
     )
 }
 
-/// Creates a `panicking` module which contains one function per GL command.
-///
-/// These functions are the mocks that are called if the real function could not be loaded.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
-where
-    W: io::Write,
-{
-    writeln!(
-        dest,
-        "#[inline(never)]
-        fn missing_fn_panic() -> ! {{
-            panic!(\"{api} function was not loaded\")
-        }}",
-        api = registry.api
-    )
-}
-
 /// Creates a structure which stores all the `FnPtr` of the bindings.
 ///
 /// The name of the struct corresponds to the namespace.
@@ -166,13 +226,18 @@ where
         }
         writeln!(dest, "pub {name}: FnPtr,", name = cmd.proto.ident)?;
     }
+    writeln!(
+        dest,
+        "/// Receives a line of output for each call.
+        _sink: __gl_imports::Arc<dyn Fn(__gl_imports::fmt::Arguments) + Send + Sync>,"
+    )?;
     writeln!(dest, "_priv: ()")?;
 
     writeln!(dest, "}}")
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_impl<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -185,7 +250,23 @@ where
             /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
             /// ~~~
             #[allow(dead_code, unused_variables)]
-            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            pub fn load_with<F>(loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                {api}::load_with_sink(loadfn, |line| println!(\"[OpenGL] {{}}\", line))
+            }}
+
+            /// Load each OpenGL symbol using a custom load function, and send the output to
+            /// `sink` instead of printing it. `sink` receives one line per call, such as
+            /// `BindBuffer(ARRAY_BUFFER, 3)`, and one line per error, and can forward them to
+            /// a `log` target or a `tracing` span.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with_sink(|s| glfw.get_proc_address(s), |line| log::trace!(target: \"gl\", \"{{}}\", line));
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with_sink<F, S>(mut loadfn: F, sink: S) -> {api}
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                      S: Fn(__gl_imports::fmt::Arguments) + Send + Sync + 'static
+            {{
                 #[inline(never)]
                 fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
                                  symbol: &'static str,
@@ -209,7 +290,8 @@ where
     for cmd in &registry.cmds {
         writeln!(
             dest,
-            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}])),",
+            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}]), \
+                missing_fns::{name} as *const __gl_imports::raw::c_void),",
             name = cmd.proto.ident,
             symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
             fallbacks = match registry.aliases.get(&cmd.proto.ident) {
@@ -218,10 +300,11 @@ where
                     .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, &name)))
                     .collect::<Vec<_>>()
                     .join(", "),
-                None => format!(""),
+                None => String::new(),
             },
         )?
     }
+    writeln!(dest, "_sink: __gl_imports::Arc::new(sink),")?;
     writeln!(dest, "_priv: ()")?;
 
     writeln!(
@@ -230,53 +313,63 @@ where
         }}"
     )?;
 
+    let get_error = registry
+        .cmds
+        .iter()
+        .find(|cmd| cmd.proto.ident == "GetError");
     for cmd in &registry.cmds {
         let idents = super::gen_parameters(cmd, true, false);
         let typed_params = super::gen_parameters(cmd, false, true);
         let println = format!(
-            "println!(\"[OpenGL] {}({})\" {});",
+            "(self._sink)(format_args!(\"{}({})\" {}));",
             cmd.proto.ident,
             (0..idents.len())
                 .map(|_| "{:?}".to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
-            idents
+            gen_debug_args(registry, cmd)
                 .iter()
-                .zip(typed_params.iter())
-                .map(|(name, ty)| if ty.contains("GLDEBUGPROC") {
-                    format!(", \"<callback>\"")
-                } else {
-                    format!(", {}", name)
-                })
+                .map(|arg| format!(", {}", arg))
                 .collect::<Vec<_>>()
                 .concat()
         );
 
+        let check =
+            super::gen_missing_fn_check(cmd, options, &format!("self.{}.f", cmd.proto.ident));
+
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
         writeln!(dest,
                       "#[allow(non_snake_case, unused_variables, dead_code)]
-            #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+            #[inline] {missing_fn_attr} pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
                 {println}
-                let r = __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
-                    (self.{name}.f)({idents});
+                {check}
+                let r = __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents});
                 {print_err}
                 r
             }}",
                       name = cmd.proto.ident,
+                      missing_fn_attr = super::gen_missing_fn_attr(options),
+                      check = check,
                       params = super::gen_parameters(cmd, true, true).join(", "),
+                      abi = super::gen_call_abi(options),
                       typed_params = typed_params.join(", "),
                       return_suffix = cmd.proto.ty,
                       idents = idents.join(", "),
                       println = println,
-                      print_err = if cmd.proto.ident != "GetError" &&
-                                     registry
-                                         .cmds
-                                         .iter()
-                                         .find(|cmd| cmd.proto.ident == "GetError")
-                                         .is_some() {
-                          format!(r#"match __gl_imports::mem::transmute::<_, extern "system" fn() -> u32>
-                    (self.GetError.f)() {{ 0 => (), r => println!("[OpenGL] ^ GL error triggered: {{}}", r) }}"#)
-                      } else {
-                          format!("")
+                      print_err = match get_error {
+                          Some(get_error) if cmd.proto.ident != "GetError" => {
+                              format!(r#"match __gl_imports::mem::transmute::<_, extern "{abi}" fn() -> {ret}>
+                    (self.GetError.f)() {{ 0 => (), r => (self._sink)(format_args!("^ GL error triggered: {{:?}}", {error})) }}"#,
+                                  abi = super::gen_call_abi(options),
+                                  ret = get_error.proto.ty,
+                                  error = match registry.api {
+                                      Api::Egl => "r",
+                                      _ => "DebugEnum(\"ErrorCode\", r)",
+                                  })
+                          },
+                          _ => String::new(),
                       })?
     }
 
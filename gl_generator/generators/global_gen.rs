@@ -14,6 +14,7 @@
 
 use registry::Registry;
 use std::io;
+use GeneratorOptions;
 
 #[allow(missing_copy_implementations)]
 pub struct GlobalGenerator;
@@ -23,16 +24,34 @@ impl super::Generator for GlobalGenerator {
     where
         W: io::Write,
     {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
         write_header(dest)?;
         write_metaloadfn(dest)?;
         write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
-        write_fns(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
         write_fnptr_struct_def(dest)?;
         write_ptrs(registry, dest)?;
         write_fn_mods(registry, dest)?;
-        write_panicking_fns(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
         write_load_fn(registry, dest)?;
+        super::gen_load_report(registry, options, dest)?;
+        write_load_with_report_fn(options, dest)?;
+        super::gen_context_info(registry, options, None, "GetStringi::is_loaded()", dest)?;
         Ok(())
     }
 }
@@ -49,6 +68,7 @@ where
         mod __gl_imports {{
             pub use std::mem;
             pub use std::os::raw;
+            pub use std::sync::atomic::{{AtomicBool, AtomicPtr, Ordering}};
         }}
     "#
     )
@@ -94,7 +114,7 @@ where
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry, dest)?;
 
     writeln!(
         dest,
@@ -105,11 +125,12 @@ where
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
         super::gen_enum_item(enm, "types::", dest)?;
     }
 
@@ -120,7 +141,7 @@ where
 ///
 /// The function calls the corresponding function pointer stored in the `storage` module created
 ///  by `write_ptrs`.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_fns<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -128,15 +149,26 @@ where
         if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
             writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
         }
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
 
+        let check = super::gen_missing_fn_check(
+            cmd,
+            options,
+            &format!("storage::{}.get()", cmd.proto.ident),
+        );
         writeln!(dest,
-            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline]
+            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline] {missing_fn_attr}
             pub unsafe fn {name}({params}) -> {return_suffix} {{ \
-                __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
-                    (storage::{name}.f)({idents}) \
+                {check} \
+                __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents}) \
             }}",
             name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
             params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
             typed_params = super::gen_parameters(cmd, false, true).join(", "),
             return_suffix = cmd.proto.ty,
             idents = super::gen_parameters(cmd, true, false).join(", "),
@@ -153,23 +185,52 @@ where
 {
     writeln!(dest,
              "
+        /// Stores a function pointer, which can be loaded from any thread.
+        ///
+        /// The function pointer doesn't guard any other data, so relaxed atomics are enough.
         #[allow(missing_copy_implementations)]
         pub struct FnPtr {{
             /// The function pointer that will be used when calling the function.
-            f: *const __gl_imports::raw::c_void,
-            /// True if the pointer points to a real function, false if points to a `panic!` fn.
-            is_loaded: bool,
+            f: __gl_imports::AtomicPtr<__gl_imports::raw::c_void>,
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
+            is_loaded: __gl_imports::AtomicBool,
         }}
 
+        #[allow(dead_code)]
         impl FnPtr {{
-            /// Creates a `FnPtr` from a load attempt.
-            pub fn new(ptr: *const __gl_imports::raw::c_void) -> FnPtr {{
+            /// Creates a `FnPtr` from a load attempt, which points to `missing` if it failed.
+            pub fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
+                let fn_ptr = FnPtr {{
+                    f: __gl_imports::AtomicPtr::new(missing as *mut __gl_imports::raw::c_void),
+                    is_loaded: __gl_imports::AtomicBool::new(false),
+                }};
+                fn_ptr.set(ptr, missing);
+                fn_ptr
+            }}
+
+            /// Stores the result of a load attempt, or `missing` if it failed.
+            fn set(&self, ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) {{
                 if ptr.is_null() {{
-                    FnPtr {{ f: missing_fn_panic as *const __gl_imports::raw::c_void, is_loaded: false }}
+                    self.f.store(missing as *mut __gl_imports::raw::c_void, __gl_imports::Ordering::Relaxed);
+                    self.is_loaded.store(false, __gl_imports::Ordering::Relaxed);
                 }} else {{
-                    FnPtr {{ f: ptr, is_loaded: true }}
+                    self.f.store(ptr as *mut __gl_imports::raw::c_void, __gl_imports::Ordering::Relaxed);
+                    self.is_loaded.store(true, __gl_imports::Ordering::Relaxed);
                 }}
             }}
+
+            /// Returns the function pointer that will be used when calling the function.
+            #[inline]
+            fn get(&self) -> *const __gl_imports::raw::c_void {{
+                self.f.load(__gl_imports::Ordering::Relaxed)
+            }}
+
+            /// Returns `true` if the function has been successfully loaded.
+            #[inline]
+            pub fn is_loaded(&self) -> bool {{
+                self.is_loaded.load(__gl_imports::Ordering::Relaxed)
+            }}
         }}
     ")
 }
@@ -184,16 +245,16 @@ where
         "mod storage {{
             #![allow(non_snake_case)]
             #![allow(non_upper_case_globals)]
-            use super::__gl_imports::raw;
+            use super::__gl_imports::{{raw, AtomicBool, AtomicPtr}};
             use super::FnPtr;"
     )?;
 
     for c in &registry.cmds {
         writeln!(
             dest,
-            "pub static mut {name}: FnPtr = FnPtr {{
-                f: super::missing_fn_panic as *const raw::c_void,
-                is_loaded: false
+            "pub static {name}: FnPtr = FnPtr {{
+                f: AtomicPtr::new(super::missing_fns::{name} as *mut raw::c_void),
+                is_loaded: AtomicBool::new(false)
             }};",
             name = c.proto.ident
         )?;
@@ -230,21 +291,21 @@ where
             r##"
             #[allow(non_snake_case)]
             pub mod {fnname} {{
-                use super::{{storage, metaloadfn}};
+                use super::{{storage, metaloadfn, missing_fns}};
                 use super::__gl_imports::raw;
-                use super::FnPtr;
 
                 #[inline]
                 #[allow(dead_code)]
                 pub fn is_loaded() -> bool {{
-                    unsafe {{ storage::{fnname}.is_loaded }}
+                    storage::{fnname}.is_loaded()
                 }}
 
                 #[allow(dead_code)]
                 pub fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const raw::c_void {{
-                    unsafe {{
-                        storage::{fnname} = FnPtr::new(metaloadfn(&mut loadfn, "{symbol}", {fallbacks}))
-                    }}
+                    storage::{fnname}.set(
+                        metaloadfn(&mut loadfn, "{symbol}", {fallbacks}),
+                        missing_fns::{fnname} as *const raw::c_void,
+                    )
                 }}
             }}
         "##,
@@ -257,24 +318,6 @@ where
     Ok(())
 }
 
-/// Creates a `missing_fn_panic` function.
-///
-/// This function is the mock that is called if the real function could not be called.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
-where
-    W: io::Write,
-{
-    writeln!(
-        dest,
-        "#[inline(never)]
-        fn missing_fn_panic() -> ! {{
-            panic!(\"{api} function was not loaded\")
-        }}
-        ",
-        api = registry.api
-    )
-}
-
 /// Creates the `load_with` function.
 ///
 /// The function calls `load_with` in each module created by `write_fn_mods`.
@@ -313,3 +356,35 @@ where
     "
     )
 }
+
+/// Creates the `load_with_report` function, if `options.load_report` is set.
+///
+/// See also `generators::gen_load_report`.
+fn write_load_with_report_fn<W>(options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !options.load_report {
+        return Ok(());
+    }
+
+    writeln!(dest,
+                  "
+        /// Load each OpenGL symbol like `load_with`, and report which functions could be loaded.
+        /// ~~~ignore
+        /// let report = gl::load_with_report(|s| glfw.get_proc_address(s));
+        /// ~~~
+        #[allow(dead_code)]
+        pub fn load_with_report<F>(mut loadfn: F) -> LoadReport where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            let mut loaded = Vec::new();
+            load_with(|symbol| {{
+                let ptr = loadfn(symbol);
+                if !ptr.is_null() {{
+                    loaded.push(symbol);
+                }}
+                ptr
+            }});
+            build_load_report(&loaded)
+        }}
+    ")
+}
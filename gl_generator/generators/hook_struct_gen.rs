@@ -0,0 +1,340 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::{Cmd, Registry};
+use std::io;
+use GeneratorOptions;
+
+/// Like `StructGenerator`, but the struct is loaded with an implementation of the generated
+/// `Hooks` trait, whose `before` and `after` methods are called around every command.
+#[allow(missing_copy_implementations)]
+pub struct HookStructGenerator;
+
+impl super::Generator for HookStructGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        super::gen_command_ids(registry, dest)?;
+        write_hooks_trait(dest)?;
+        write_fnptr_struct_def(dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        write_struct(registry, dest)?;
+        write_impl(registry, options, dest)?;
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            pub use std::fmt;
+            pub use std::mem;
+            pub use std::marker::Send;
+            pub use std::os::raw;
+            pub use std::sync::Arc;
+        }}
+    "#
+    )
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `Hooks` trait, and the `Args` structure that is passed to `Hooks::before`.
+fn write_hooks_trait<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Callbacks that are called around every command.
+        ///
+        /// Both methods do nothing by default.
+        pub trait Hooks {{
+            /// Called before the command, with its arguments, which are formatted like
+            /// `(34962, 3)`.
+            #[allow(unused_variables)]
+            fn before(&self, cmd: CommandId, args: &dyn __gl_imports::fmt::Debug) {{}}
+
+            /// Called after the command, with its return value, which is `()` for the commands
+            /// that don't return anything.
+            #[allow(unused_variables)]
+            fn after(&self, cmd: CommandId, ret: &dyn __gl_imports::fmt::Debug) {{}}
+        }}
+
+        /// The arguments of a command.
+        struct Args<'a>(&'a [&'a dyn __gl_imports::fmt::Debug]);
+
+        impl<'a> __gl_imports::fmt::Debug for Args<'a> {{
+            fn fmt(&self, f: &mut __gl_imports::fmt::Formatter) -> __gl_imports::fmt::Result {{
+                f.write_str(\"(\")?;
+                for (i, arg) in self.0.iter().enumerate() {{
+                    if i > 0 {{
+                        f.write_str(\", \")?;
+                    }}
+                    __gl_imports::fmt::Debug::fmt(arg, f)?;
+                }}
+                f.write_str(\")\")
+            }}
+        }}
+    "
+    )
+}
+
+/// Returns the expression that is passed to `Hooks::before` for each argument of `cmd`.
+///
+/// Callbacks are passed as a placeholder.
+fn gen_hook_args(cmd: &Cmd) -> Vec<String> {
+    cmd.params
+        .iter()
+        .map(|binding| {
+            if binding.ty.contains("GLDEBUGPROC") {
+                "&\"<callback>\"".to_string()
+            } else {
+                format!("&{}", binding.ident)
+            }
+        })
+        .collect()
+}
+
+/// Creates a `FnPtr` structure which contains the store for a single binding.
+fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(dead_code, missing_copy_implementations)]
+        #[derive(Clone)]
+        pub struct FnPtr {{
+            /// The function pointer that will be used when calling the function.
+            f: *const __gl_imports::raw::c_void,
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
+            is_loaded: bool,
+        }}
+
+        impl FnPtr {{
+            /// Creates a `FnPtr` from a load attempt.
+            fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
+                if ptr.is_null() {{
+                    FnPtr {{ f: missing, is_loaded: false }}
+                }} else {{
+                    FnPtr {{ f: ptr, is_loaded: true }}
+                }}
+            }}
+
+            /// Returns `true` if the function has been successfully loaded.
+            ///
+            /// If it returns `false`, calling the corresponding function will fail.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn is_loaded(&self) -> bool {{
+                self.is_loaded
+            }}
+        }}
+    "
+    )
+}
+
+/// Creates a structure which stores all the `FnPtr` of the bindings, and the hooks.
+///
+/// The name of the struct corresponds to the namespace.
+fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        #[derive(Clone)]
+        pub struct {api} {{",
+        api = super::gen_struct_name(registry.api)
+    )?;
+
+    for cmd in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        writeln!(dest, "pub {name}: FnPtr,", name = cmd.proto.ident)?;
+    }
+    writeln!(
+        dest,
+        "/// Called around each command.
+        _hooks: __gl_imports::Arc<dyn Hooks + Send + Sync>,"
+    )?;
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `impl` of the structure created by `write_struct`.
+fn write_impl<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol using a custom load function, and call the methods of
+            /// `hooks` around each command.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with_hooks(|s| glfw.get_proc_address(s), CallCounter::default());
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with_hooks<F, H>(mut loadfn: F, hooks: H) -> {api}
+                where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                      H: Hooks + Send + Sync + 'static
+            {{
+                #[inline(never)]
+                fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                                 symbol: &'static str,
+                                 symbols: &[&'static str])
+                                 -> *const __gl_imports::raw::c_void {{
+                    let mut ptr = loadfn(symbol);
+                    if ptr.is_null() {{
+                        for &sym in symbols {{
+                            ptr = loadfn(sym);
+                            if !ptr.is_null() {{ break; }}
+                        }}
+                    }}
+                    ptr
+                }}
+                let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
+                    do_metaloadfn(&mut loadfn, symbol, symbols)
+                }};
+                {api} {{",
+                  api = super::gen_struct_name(registry.api))?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}]), \
+                missing_fns::{name} as *const __gl_imports::raw::c_void),",
+            name = cmd.proto.ident,
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks = match registry.aliases.get(&cmd.proto.ident) {
+                Some(fbs) => fbs
+                    .iter()
+                    .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, name)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                None => String::new(),
+            },
+        )?
+    }
+    writeln!(dest, "_hooks: __gl_imports::Arc::new(hooks),")?;
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(
+        dest,
+        "}}
+        }}"
+    )?;
+
+    for cmd in &registry.cmds {
+        let check =
+            super::gen_missing_fn_check(cmd, options, &format!("self.{}.f", cmd.proto.ident));
+
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+        writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline] {missing_fn_attr} pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+                self._hooks.before(CommandId::{name}, &Args(&[{args}]));
+                {check}
+                let r = __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents});
+                self._hooks.after(CommandId::{name}, &r);
+                r
+            }}",
+            name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+            args = gen_hook_args(cmd).join(", "),
+        )?
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        unsafe impl __gl_imports::Send for {api} {{}}",
+        api = super::gen_struct_name(registry.api)
+    )
+}
@@ -0,0 +1,348 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+use GeneratorOptions;
+
+/// Like `GlobalGenerator`, but `load_with` only stores the load function, and each function is
+/// loaded the first time it is called.
+///
+/// `preload` and `preload_all` can still be used to load functions ahead of time.
+#[allow(missing_copy_implementations)]
+pub struct LazyGenerator;
+
+impl super::Generator for LazyGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
+        write_symbols(registry, dest)?;
+        write_storage(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        super::gen_missing_fn_ptrs(registry, dest)?;
+        write_load_fn(dest)?;
+        write_preload_fns(dest)?;
+        super::gen_context_info(
+            registry,
+            options,
+            None,
+            &format!(
+                "is_loaded(\"{}\")",
+                super::gen_symbol_name(registry.api, "GetStringi")
+            ),
+            dest,
+        )?;
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            pub use std::mem;
+            pub use std::os::raw;
+            pub use std::ptr;
+            pub use std::sync::atomic::{{AtomicPtr, Ordering}};
+            pub use std::sync::{{Mutex, PoisonError}};
+        }}
+    "#
+    )
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(
+        dest,
+        "
+        }}
+    "
+    )
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the functions corresponding to the GL commands.
+///
+/// Each function calls the pointer at its index in the table of the `storage` module created by
+///  `write_storage`, which loads it on the first call.
+fn write_fns<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for (index, cmd) in registry.cmds.iter().enumerate() {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+
+        let check = super::gen_missing_fn_check(cmd, options, &format!("storage::get({})", index));
+        writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline] {missing_fn_attr}
+            pub unsafe fn {name}({params}) -> {return_suffix} {{ \
+                {check} \
+                __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents}) \
+            }}",
+            name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `SYMBOLS` table.
+///
+/// The table has one entry per command, in the order of the pointer table. Each entry holds the
+///  symbol of the command, followed by its fallbacks.
+fn write_symbols<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "static SYMBOLS: [&[&str]; {len}] = [",
+        len = registry.cmds.len()
+    )?;
+
+    for c in &registry.cmds {
+        let mut symbols = vec![super::gen_symbol_name(registry.api, &c.proto.ident)];
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            symbols.extend(
+                v.iter()
+                    .map(|name| super::gen_symbol_name(registry.api, name)),
+            );
+        }
+        let symbols = symbols
+            .iter()
+            .map(|symbol| format!("\"{}\"", symbol))
+            .collect::<Vec<_>>();
+        writeln!(dest, "&[{}],", symbols.join(", "))?;
+    }
+
+    writeln!(dest, "];")
+}
+
+/// Creates a `storage` module which contains the load function, and the table of function
+///  pointers which are loaded with it on demand.
+fn write_storage<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        mod storage {{
+            #![allow(clippy::declare_interior_mutable_const)]
+            use super::__gl_imports::{{ptr, raw, AtomicPtr, Mutex, Ordering, PoisonError}};
+
+            pub type LoadFn = Box<dyn FnMut(&'static str) -> *const raw::c_void + Send>;
+
+            const NOT_RESOLVED: AtomicPtr<raw::c_void> = AtomicPtr::new(ptr::null_mut());
+
+            /// The function pointers that will be used when calling the functions, or null if
+            /// they haven't been loaded yet.
+            ///
+            /// The function pointers don't guard any other data, so relaxed atomics are enough.
+            static FNS: [AtomicPtr<raw::c_void>; {len}] = [NOT_RESOLVED; {len}];
+            /// The load function passed to `load_with`. The lock is held while a function is
+            /// loaded, so that the load function is never called from two threads at once.
+            static LOAD_FN: Mutex<Option<LoadFn>> = Mutex::new(None);
+
+            /// Replaces the load function, and forgets the functions loaded with the previous one.
+            pub fn set_load_fn(loadfn: LoadFn) {{
+                let mut load_fn = LOAD_FN.lock().unwrap_or_else(PoisonError::into_inner);
+                *load_fn = Some(loadfn);
+                for f in FNS.iter() {{
+                    f.store(ptr::null_mut(), Ordering::Relaxed);
+                }}
+            }}
+
+            /// Returns the function pointer that will be used when calling the function, and
+            /// loads it if this is the first call.
+            #[inline]
+            pub fn get(index: usize) -> *const raw::c_void {{
+                let ptr = FNS[index].load(Ordering::Relaxed);
+                if ptr.is_null() {{
+                    resolve(index)
+                }} else {{
+                    ptr
+                }}
+            }}
+
+            /// Loads a function with the symbols of its `SYMBOLS` entry, trying each fallback in
+            /// turn, and stores the result in the table.
+            #[cold]
+            #[inline(never)]
+            fn resolve(index: usize) -> *const raw::c_void {{
+                let mut load_fn = LOAD_FN.lock().unwrap_or_else(PoisonError::into_inner);
+                // another thread may have loaded the function while we were waiting for the lock
+                let ptr = FNS[index].load(Ordering::Relaxed);
+                if !ptr.is_null() {{
+                    return ptr;
+                }}
+                // the function is loaded again once `load_with` has been called
+                let loadfn = match *load_fn {{
+                    Some(ref mut loadfn) => loadfn,
+                    None => return super::missing_fn_ptr(index),
+                }};
+
+                let mut ptr = ptr::null();
+                for &symbol in super::SYMBOLS[index] {{
+                    ptr = loadfn(symbol);
+                    if !ptr.is_null() {{ break; }}
+                }}
+                if ptr.is_null() {{
+                    ptr = super::missing_fn_ptr(index);
+                }}
+                FNS[index].store(ptr as *mut raw::c_void, Ordering::Relaxed);
+                ptr
+            }}
+
+            /// Returns `true` if the function could be loaded, loading it if needed.
+            pub fn is_loaded(index: usize) -> bool {{
+                get(index) != super::missing_fn_ptr(index)
+            }}
+        }}
+    ",
+        len = registry.cmds.len()
+    )
+}
+
+/// Creates the `load_with` function.
+///
+/// The function only stores the load function, which is then called by the `storage` module
+///  created by `write_storage`.
+fn write_load_fn<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "
+        /// Sets the function used to load the OpenGL symbols. This allows for the
+        /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+        ///
+        /// Each symbol is loaded the first time its function is called, so the load function
+        /// must stay valid for as long as the bindings are used, and it must not call them.
+        /// ~~~ignore
+        /// gl::load_with(|s| glfw::get_proc_address_raw(s));
+        /// ~~~
+        #[allow(dead_code)]
+        pub fn load_with<F>(loadfn: F) where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void + Send + 'static {{
+            storage::set_load_fn(Box::new(loadfn))
+        }}
+    ")
+}
+
+/// Creates the `is_loaded`, `preload` and `preload_all` functions, which load commands ahead of
+///  their first call.
+fn write_preload_fns<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Returns the index of the command with the given symbol in `SYMBOLS`.
+        #[allow(dead_code)]
+        fn symbol_index(symbol: &str) -> Option<usize> {{
+            SYMBOLS.iter().position(|symbols| symbols[0] == symbol)
+        }}
+
+        /// Returns `true` if the function with the given symbol, such as `\"glClear\"`, could be
+        /// loaded. The function is loaded if it hasn't been called yet.
+        #[allow(dead_code)]
+        pub fn is_loaded(symbol: &str) -> bool {{
+            symbol_index(symbol).map_or(false, storage::is_loaded)
+        }}
+
+        /// Loads the functions with the given symbols, such as `\"glClear\"`, now instead of on
+        /// their first call. Unknown symbols are ignored.
+        #[allow(dead_code)]
+        pub fn preload(symbols: &[&str]) {{
+            for index in symbols.iter().filter_map(|symbol| symbol_index(symbol)) {{
+                storage::get(index);
+            }}
+        }}
+
+        /// Loads all the functions now instead of on their first call.
+        #[allow(dead_code)]
+        pub fn preload_all() {{
+            for index in 0..SYMBOLS.len() {{
+                storage::get(index);
+            }}
+        }}
+    "
+    )
+}
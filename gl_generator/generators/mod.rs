@@ -12,15 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use registry::{Cmd, Enum, Registry};
+use registry::{Cmd, Enum, Group, Registry, TypeDef};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use Api;
 
+pub mod checked_struct_gen;
 pub mod debug_struct_gen;
 pub mod global_gen;
+pub mod hook_struct_gen;
+pub mod lazy_gen;
 pub mod static_gen;
 pub mod static_struct_gen;
+pub mod stats_struct_gen;
 pub mod struct_gen;
+pub mod table_gen;
+pub mod thread_local_gen;
+pub mod trait_gen;
 
 /// Trait for a bindings generator.
 ///
@@ -30,6 +39,120 @@ pub trait Generator {
     fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
     where
         W: io::Write;
+
+    /// Builds the GL bindings, taking `options` into account.
+    ///
+    /// The default implementation ignores the options and calls `write`.
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let _ = options;
+        self.write(registry, dest)
+    }
+}
+
+/// Options that change the code emitted by the built-in generators.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeneratorOptions {
+    /// Mark the commands and enums that were removed from the core profile with
+    /// `#[deprecated]`. Only has an effect on registries built for `Profile::Compatibility`.
+    pub deprecate_removed: bool,
+    /// Generate a `groups` module with a newtype for each group of enums and a flags type for
+    /// each bitmask group, and use those types for the command parameters that belong to a
+    /// group.
+    pub typed_groups: bool,
+    /// Generate the `enum_name` and `enum_name_in` functions, which return the name of an enum
+    /// from its value.
+    pub enum_names: bool,
+    /// Generate a `load_with_report` function, which also returns a `LoadReport` that lists the
+    /// functions that could not be loaded, and the versions and extensions that are available.
+    ///
+    /// Only the global, table and struct generators support this option.
+    pub load_report: bool,
+    /// Generate a `ContextInfo` type, which queries the version and the extensions of the
+    /// current context.
+    ///
+    /// Only the global, table, lazy, thread-local and struct generators support this option, for
+    /// OpenGL and OpenGL ES.
+    pub context_info: bool,
+    /// What happens when a function that could not be loaded is called.
+    pub missing_fn: MissingFn,
+}
+
+/// What happens when a function that could not be loaded is called.
+///
+/// The functions that could not be loaded point to a stub for their command, which handles the
+/// call out of line, so calling a loaded function costs the same as without the stubs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingFn {
+    /// Panic with the name of the function, such as `gl::Viewport was not loaded`.
+    ///
+    /// The panic unwinds through the function pointer, so the bindings call the function
+    /// pointers through the `"system-unwind"` ABI, which needs Rust 1.71.
+    #[default]
+    Panic,
+    /// Like `Panic`, but at the location of the call. Each generated function compares the
+    /// function pointer with the stub before calling it, and is `#[track_caller]`, which adds a
+    /// hidden argument to each call.
+    PanicAtCaller,
+    /// Do nothing, and return zero.
+    NoOp,
+    /// Record the name of the function, which is returned by the generated `take_missing_fn`
+    /// function, and return zero.
+    Error,
+    /// Print the name of the function to stderr, and abort the process.
+    Abort,
+}
+
+/// Applies the options that change how commands are declared.
+///
+/// With `options.typed_groups`, the `GLenum` and `GLbitfield` parameters that belong to a group
+/// are given the group's type from the `groups` module. See `gen_groups`.
+pub fn apply_options<'a>(registry: &'a Registry, options: &GeneratorOptions) -> Cow<'a, Registry> {
+    if !options.typed_groups {
+        return Cow::Borrowed(registry);
+    }
+
+    let cmds = registry
+        .cmds
+        .iter()
+        .cloned()
+        .map(|mut cmd| {
+            for param in &mut cmd.params {
+                let newtype = match param.group.as_ref().and_then(|g| registry.groups.get(g)) {
+                    Some(group) if param.ty == "types::GLenum" && !is_bitmask_group(group) => {
+                        Some(format!("groups::{}", group.ident))
+                    },
+                    Some(group) if param.ty == "types::GLbitfield" && is_bitmask_group(group) => {
+                        Some(format!("groups::{}", group.ident))
+                    },
+                    _ => None,
+                };
+                if let Some(newtype) = newtype {
+                    param.ty = Cow::Owned(newtype);
+                }
+            }
+            cmd
+        })
+        .collect();
+    Cow::Owned(Registry {
+        cmds,
+        ..registry.clone()
+    })
+}
+
+/// Returns `true` for the groups whose members are combined as bit flags.
+fn is_bitmask_group(group: &Group) -> bool {
+    group
+        .enums_type
+        .as_ref()
+        .map_or(false, |ty| ty == "bitmask")
 }
 
 pub fn gen_struct_name(api: Api) -> &'static str {
@@ -45,11 +168,230 @@ pub fn gen_struct_name(api: Api) -> &'static str {
     }
 }
 
+/// Generates a doc comment saying where an enum or command comes from.
+///
+/// Example result: `/// Core since 4.5; also GL_ARB_direct_state_access`.
+pub fn gen_provenance_doc<W>(
+    introduced_in: Option<(u8, u8)>,
+    extensions: &[String],
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    match (introduced_in, extensions.is_empty()) {
+        (Some((major, minor)), true) => writeln!(dest, "/// Core since {}.{}", major, minor),
+        (Some((major, minor)), false) => writeln!(
+            dest,
+            "/// Core since {}.{}; also {}",
+            major,
+            minor,
+            extensions.join(", ")
+        ),
+        (None, false) => writeln!(dest, "/// Provided by {}", extensions.join(", ")),
+        (None, true) => Ok(()),
+    }
+}
+
+/// Generates a `#[deprecated]` attribute for an enum or command that was removed from the core
+/// profile, if `options.deprecate_removed` is set.
+pub fn gen_deprecated_attr<W>(
+    removed_in: Option<(u8, u8)>,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    match removed_in {
+        Some((major, minor)) if options.deprecate_removed => writeln!(
+            dest,
+            "#[deprecated(note = \"removed from core in {}.{}\")]",
+            major, minor
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Generates the `missing_fn` function, which handles a call to a function that could not be
+/// loaded according to `options.missing_fn`, and the `missing_fns` module, which has a stub for
+/// each command that calls `missing_fn` with the name of the command.
+///
+/// The address of the stub of a command is stored for it when it could not be loaded. With
+/// `MissingFn::Error`, also generates the `take_missing_fn` function.
+pub fn gen_missing_fns<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    match options.missing_fn {
+        MissingFn::Panic => writeln!(
+            dest,
+            "#[cold]
+            #[inline(never)]
+            fn missing_fn(name: &'static str) -> ! {{
+                panic!(\"{api}::{{}} was not loaded\", name)
+            }}",
+            api = registry.api
+        )?,
+        MissingFn::PanicAtCaller => writeln!(
+            dest,
+            "#[cold]
+            #[inline(never)]
+            #[track_caller]
+            fn missing_fn(name: &'static str) -> ! {{
+                panic!(\"{api}::{{}} was not loaded\", name)
+            }}",
+            api = registry.api
+        )?,
+        MissingFn::NoOp => {},
+        MissingFn::Error => writeln!(
+            dest,
+            "thread_local! {{
+                static MISSING_FN: ::std::cell::Cell<Option<&'static str>> =
+                    ::std::cell::Cell::new(None);
+            }}
+
+            #[cold]
+            #[inline(never)]
+            fn missing_fn(name: &'static str) {{
+                MISSING_FN.with(|missing_fn| missing_fn.set(Some(name)));
+            }}
+
+            /// Returns the name of the last function that was called on this thread without
+            /// having been loaded, such as `\"Viewport\"`, and clears it.
+            #[allow(dead_code)]
+            pub fn take_missing_fn() -> Option<&'static str> {{
+                MISSING_FN.with(|missing_fn| missing_fn.take())
+            }}"
+        )?,
+        MissingFn::Abort => writeln!(
+            dest,
+            "#[cold]
+            #[inline(never)]
+            fn missing_fn(name: &'static str) -> ! {{
+                eprintln!(\"{api}::{{}} was not loaded\", name);
+                ::std::process::abort()
+            }}",
+            api = registry.api
+        )?,
+    }
+
+    writeln!(
+        dest,
+        "mod missing_fns {{
+            #![allow(non_snake_case, unused_variables, dead_code, unused_imports)]
+            use super::__gl_imports;
+            use super::types;"
+    )?;
+    if options.typed_groups {
+        writeln!(dest, "use super::groups;")?;
+    }
+    for cmd in &registry.cmds {
+        let body = match options.missing_fn {
+            MissingFn::Panic | MissingFn::PanicAtCaller | MissingFn::Abort => {
+                format!("super::missing_fn(\"{}\")", cmd.proto.ident)
+            },
+            MissingFn::NoOp => "unsafe { __gl_imports::mem::zeroed() }".to_string(),
+            MissingFn::Error => format!(
+                "super::missing_fn(\"{}\"); unsafe {{ __gl_imports::mem::zeroed() }}",
+                cmd.proto.ident
+            ),
+        };
+        writeln!(
+            dest,
+            "#[cold]
+            #[inline(never)]
+            pub extern \"{abi}\" fn {name}({params}) -> {return_suffix} {{ {body} }}",
+            abi = gen_call_abi(options),
+            name = cmd.proto.ident,
+            params = gen_parameters(cmd, true, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            body = body,
+        )?;
+    }
+    writeln!(dest, "}}")
+}
+
+/// Generates the `missing_fn_ptr` function, which returns the address of the stub of the command
+/// at the given index in `registry.cmds`, for the generators that store the function pointers
+/// in a table.
+///
+/// See also `gen_missing_fns`.
+pub fn gen_missing_fn_ptrs<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "/// Returns the stub that is called instead of the command at `index` when it could not be
+        /// loaded.
+        #[cold]
+        #[inline(never)]
+        fn missing_fn_ptr(index: usize) -> *const __gl_imports::raw::c_void {{
+            match index {{"
+    )?;
+    for (index, cmd) in registry.cmds.iter().enumerate() {
+        writeln!(
+            dest,
+            "{} => missing_fns::{} as *const __gl_imports::raw::c_void,",
+            index, cmd.proto.ident
+        )?;
+    }
+    writeln!(
+        dest,
+        "_ => unreachable!(),
+            }}
+        }}"
+    )
+}
+
+/// Returns the ABI through which the generated functions call the function pointers.
+///
+/// The stubs of `MissingFn::Panic` unwind, which is only allowed through an `-unwind` ABI.
+pub fn gen_call_abi(options: &GeneratorOptions) -> &'static str {
+    match options.missing_fn {
+        MissingFn::Panic => "system-unwind",
+        _ => "system",
+    }
+}
+
+/// Generates the statement that loads the function pointer of `cmd` from the `ptr` expression
+/// into `fn_ptr`.
+///
+/// With `MissingFn::PanicAtCaller`, the statement also panics if the function was not loaded.
+/// The functions using it should then have the attribute returned by `gen_missing_fn_attr`.
+pub fn gen_missing_fn_check(cmd: &Cmd, options: &GeneratorOptions, ptr: &str) -> String {
+    match options.missing_fn {
+        MissingFn::PanicAtCaller => format!(
+            "let fn_ptr = {ptr}; \
+             if fn_ptr == missing_fns::{name} as *const __gl_imports::raw::c_void {{ \
+                 missing_fn(\"{name}\"); \
+             }}",
+            ptr = ptr,
+            name = cmd.proto.ident
+        ),
+        _ => format!("let fn_ptr = {};", ptr),
+    }
+}
+
+/// Returns `#[track_caller]` if `options.missing_fn` panics at the location of the call.
+pub fn gen_missing_fn_attr(options: &GeneratorOptions) -> &'static str {
+    match options.missing_fn {
+        MissingFn::PanicAtCaller => "#[track_caller]",
+        _ => "",
+    }
+}
+
 /// This function generates a `const name: type = value;` item.
 pub fn gen_enum_item<W>(enm: &Enum, types_prefix: &str, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
+    gen_provenance_doc(enm.introduced_in, &enm.extensions, dest)?;
     writeln!(dest,
         "#[allow(dead_code, non_upper_case_globals)] pub const {ident}: {types_prefix}{ty} = {value}{cast_suffix};",
         ident = enm.ident,
@@ -63,25 +405,852 @@ where
     )
 }
 
-/// Generates all the type aliases for a namespace.
+/// Generates the `groups` module, which has a `#[repr(transparent)]` newtype for each group of
+/// enums, with the group's members as associated constants.
 ///
-/// Aliases are either `pub type = ...` or `#[repr(C)] pub struct ... { ... }` and contain all the
-/// things that we can't obtain from the XML files.
-pub fn gen_types<W>(api: Api, dest: &mut W) -> io::Result<()>
+/// The types of the bitmask groups wrap a `GLbitfield` and can be combined with `|` and `&`.
+///
+/// The module is only generated if `options.typed_groups` is set.
+pub fn gen_groups<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
 where
     W: io::Write,
 {
-    if let Api::Egl = api {
-        writeln!(dest, "{}", include_str!("templates/types/egl.rs"))?;
+    if !options.typed_groups {
         return Ok(());
     }
 
-    writeln!(dest, "{}", include_str!("templates/types/gl.rs"))?;
+    writeln!(
+        dest,
+        "pub mod groups {{
+            #![allow(dead_code, non_upper_case_globals)]
+            use std::fmt;
+            use std::ops;
+            use super::types;"
+    )?;
 
-    match api {
-        Api::Glx => writeln!(dest, "{}", include_str!("templates/types/glx.rs"))?,
-        Api::Wgl => writeln!(dest, "{}", include_str!("templates/types/wgl.rs"))?,
-        _ => {},
+    let enums: BTreeMap<&str, &Enum> = registry
+        .enums
+        .iter()
+        .filter(|e| e.ty == "GLenum" || e.ty == "GLuint")
+        .map(|e| (&e.ident[..], e))
+        .collect();
+
+    for group in registry.groups.values() {
+        // the registry lists some members of a group more than once
+        let mut idents = BTreeSet::new();
+        let members: Vec<&Enum> = group
+            .enums
+            .iter()
+            .filter(|ident| idents.insert(&ident[..]))
+            .filter_map(|ident| enums.get(&ident[..]).cloned())
+            .collect();
+
+        if is_bitmask_group(group) {
+            gen_bitmask_group(group, &members, options, dest)?;
+            continue;
+        }
+
+        writeln!(
+            dest,
+            "#[repr(transparent)]
+            #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+            pub struct {group}(pub types::GLenum);
+
+            impl {group} {{",
+            group = group.ident,
+        )?;
+        for enm in &members {
+            gen_provenance_doc(enm.introduced_in, &enm.extensions, dest)?;
+            gen_deprecated_attr(enm.removed_in, options, dest)?;
+            writeln!(
+                dest,
+                "pub const {ident}: {group} = {group}({value});",
+                ident = enm.ident,
+                group = group.ident,
+                value = enm.value,
+            )?;
+        }
+        writeln!(dest, "}}")?;
+
+        // several enums can share a value, in which case the first one is printed
+        let mut values = BTreeSet::new();
+        writeln!(
+            dest,
+            "impl fmt::Debug for {group} {{
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{
+                    match self.0 {{",
+            group = group.ident,
+        )?;
+        for enm in members.iter().filter(|e| values.insert(&e.value)) {
+            writeln!(dest, "{} => f.write_str(\"{}\"),", enm.value, enm.ident)?;
+        }
+        writeln!(
+            dest,
+            "value => write!(f, \"{group}({{:#x}})\", value),
+                    }}
+                }}
+            }}",
+            group = group.ident,
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Generates the flags type of a bitmask group, for `gen_groups`.
+fn gen_bitmask_group<W>(
+    group: &Group,
+    members: &[&Enum],
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "#[repr(transparent)]
+        #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+        pub struct {group}(pub types::GLbitfield);
+
+        impl {group} {{",
+        group = group.ident,
+    )?;
+    for enm in members {
+        gen_provenance_doc(enm.introduced_in, &enm.extensions, dest)?;
+        gen_deprecated_attr(enm.removed_in, options, dest)?;
+        writeln!(
+            dest,
+            "pub const {ident}: {group} = {group}({value});",
+            ident = enm.ident,
+            group = group.ident,
+            value = enm.value,
+        )?;
+    }
+    writeln!(
+        dest,
+        "
+            /// Returns a value with no flags set.
+            #[inline]
+            pub fn empty() -> {group} {{ {group}(0) }}
+
+            /// Returns the raw value of the flags.
+            #[inline]
+            pub fn bits(self) -> types::GLbitfield {{ self.0 }}
+
+            /// Returns `true` if no flags are set.
+            #[inline]
+            pub fn is_empty(self) -> bool {{ self.0 == 0 }}
+
+            /// Returns `true` if all the flags of `other` are set in `self`.
+            #[inline]
+            pub fn contains(self, other: {group}) -> bool {{ self.0 & other.0 == other.0 }}
+        }}
+
+        impl ops::BitOr for {group} {{
+            type Output = {group};
+            #[inline]
+            fn bitor(self, other: {group}) -> {group} {{ {group}(self.0 | other.0) }}
+        }}
+
+        impl ops::BitOrAssign for {group} {{
+            #[inline]
+            fn bitor_assign(&mut self, other: {group}) {{ self.0 |= other.0; }}
+        }}
+
+        impl ops::BitAnd for {group} {{
+            type Output = {group};
+            #[inline]
+            fn bitand(self, other: {group}) -> {group} {{ {group}(self.0 & other.0) }}
+        }}
+
+        impl ops::BitAndAssign for {group} {{
+            #[inline]
+            fn bitand_assign(&mut self, other: {group}) {{ self.0 &= other.0; }}
+        }}
+
+        impl fmt::Debug for {group} {{
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{
+                const FLAGS: &[(&str, types::GLbitfield)] = &[",
+        group = group.ident,
+    )?;
+    for enm in members {
+        writeln!(dest, "(\"{}\", {}),", enm.ident, enm.value)?;
+    }
+    writeln!(
+        dest,
+        "];
+                let mut remaining = self.0;
+                let mut first = true;
+                for &(name, bits) in FLAGS {{
+                    if bits != 0 && remaining & bits == bits {{
+                        if !first {{ f.write_str(\" | \")?; }}
+                        f.write_str(name)?;
+                        remaining &= !bits;
+                        first = false;
+                    }}
+                }}
+                if remaining != 0 || first {{
+                    if !first {{ f.write_str(\" | \")?; }}
+                    write!(f, \"{{:#x}}\", remaining)?;
+                }}
+                Ok(())
+            }}
+        }}"
+    )
+}
+
+/// Generates the `enum_name` and `enum_name_in` functions, if `options.enum_names` is set.
+///
+/// When several enums share a value, `enum_name` picks the one that is not an alias and was
+/// introduced by a version rather than an extension. Members of bitmask groups are only picked
+/// if no other enum has the value.
+pub fn gen_enum_names<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    // EGL has no `GLenum`
+    if !options.enum_names || registry.api == Api::Egl {
+        return Ok(());
+    }
+
+    let bitmask_members: BTreeSet<&str> = registry
+        .groups
+        .values()
+        .filter(|g| is_bitmask_group(g))
+        .flat_map(|g| g.enums.iter().map(|ident| &ident[..]))
+        .collect();
+    let mut enums: Vec<(u64, &Enum)> = registry
+        .enums
+        .iter()
+        .filter(|e| e.ty == "GLenum")
+        .filter_map(|e| parse_enum_value(&e.value).map(|value| (value, e)))
+        .collect();
+    enums.sort_by_key(|&(_, e)| {
+        (
+            bitmask_members.contains(&e.ident[..]),
+            e.alias.is_some(),
+            e.introduced_in.is_none(),
+        )
+    });
+
+    let mut names = BTreeMap::new();
+    for &(value, enm) in &enums {
+        names.entry(value).or_insert(&enm.ident);
+    }
+
+    writeln!(
+        dest,
+        "/// Returns the name of the enum with the given value, if there is one.
+        #[allow(dead_code)]
+        pub fn enum_name(value: types::GLenum) -> Option<&'static str> {{
+            match value {{"
+    )?;
+    for (value, ident) in &names {
+        writeln!(dest, "{:#x} => Some(\"{}\"),", value, ident)?;
+    }
+    writeln!(
+        dest,
+        "_ => None,
+            }}
+        }}
+
+        /// Returns the name of the enum with the given value in `group`, if there is one.
+        #[allow(dead_code)]
+        pub fn enum_name_in(group: &str, value: types::GLenum) -> Option<&'static str> {{
+            match (group, value) {{"
+    )?;
+    let ranks: BTreeMap<&str, usize> = enums
+        .iter()
+        .enumerate()
+        .map(|(rank, &(_, e))| (&e.ident[..], rank))
+        .collect();
+    for group in registry.groups.values() {
+        let mut members: Vec<usize> = group
+            .enums
+            .iter()
+            .filter_map(|ident| ranks.get(&ident[..]).cloned())
+            .collect();
+        members.sort();
+
+        let mut names = BTreeMap::new();
+        for &rank in &members {
+            let (value, enm) = enums[rank];
+            names.entry(value).or_insert(&enm.ident);
+        }
+        for (value, ident) in &names {
+            writeln!(
+                dest,
+                "(\"{}\", {:#x}) => Some(\"{}\"),",
+                group.ident, value, ident
+            )?;
+        }
+    }
+    writeln!(
+        dest,
+        "_ => None,
+            }}
+        }}"
+    )
+}
+
+/// Generates the `CommandId` enum, which has one variant per command, in the order of
+/// `registry.cmds`.
+pub fn gen_command_ids<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Identifies a command of the bindings.
+        #[allow(non_camel_case_types, dead_code)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum CommandId {{"
+    )?;
+    for cmd in &registry.cmds {
+        writeln!(dest, "{},", cmd.proto.ident)?;
+    }
+    writeln!(
+        dest,
+        "}}
+
+        #[allow(dead_code)]
+        impl CommandId {{
+            /// All the commands, in the order of their variants.
+            pub const ALL: &[CommandId] = &["
+    )?;
+    for cmd in &registry.cmds {
+        writeln!(dest, "CommandId::{},", cmd.proto.ident)?;
+    }
+    writeln!(
+        dest,
+        "];
+
+            /// Returns the name of the command, such as `\"Clear\"`.
+            pub fn name(self) -> &'static str {{
+                match self {{"
+    )?;
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "CommandId::{name} => \"{name}\",",
+            name = cmd.proto.ident
+        )?;
+    }
+    writeln!(
+        dest,
+        "}}
+            }}
+
+            /// Returns the version that introduced the command, or `None` if it is only provided
+            /// by extensions.
+            pub fn introduced_in(self) -> Option<(u8, u8)> {{
+                match self {{"
+    )?;
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "CommandId::{} => {:?},",
+            cmd.proto.ident, cmd.introduced_in
+        )?;
+    }
+    writeln!(
+        dest,
+        "}}
+            }}
+        }}"
+    )
+}
+
+/// Generates the `LoadReport` type and the `build_load_report` function, if `options.load_report`
+/// is set.
+///
+/// `build_load_report` takes the symbols that the loader could find, and is meant to be called by
+/// the `load_with_report` function of the generator.
+pub fn gen_load_report<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !options.load_report {
+        return Ok(());
+    }
+
+    let versions: BTreeSet<(u8, u8)> = registry
+        .cmds
+        .iter()
+        .filter_map(|cmd| cmd.introduced_in)
+        .chain(registry.enums.iter().filter_map(|enm| enm.introduced_in))
+        .collect();
+    let gen_features = |introduced_in: Option<(u8, u8)>, extensions: &[String]| {
+        introduced_in
+            .iter()
+            .map(|&(major, minor)| format!("Feature::Version({}, {})", major, minor))
+            .chain(
+                extensions
+                    .iter()
+                    .map(|ext| format!("Feature::Extension(\"{}\")", ext)),
+            )
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    writeln!(
+        dest,
+        r#"
+        /// A version or an extension of the API.
+        #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum Feature {{
+            Version(u8, u8),
+            Extension(&'static str),
+        }}
+
+        /// How much of a version or an extension could be loaded.
+        ///
+        /// Versions and extensions that have no functions are always `Full`.
+        #[allow(dead_code)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Availability {{
+            /// All of the functions were loaded.
+            Full,
+            /// Some of the functions were loaded.
+            Partial,
+            /// None of the functions were loaded.
+            Unavailable,
+        }}
+
+        /// How a function was loaded.
+        #[allow(dead_code)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct FnReport {{
+            /// The symbol of the function, such as `glGenFramebuffers`.
+            pub symbol: &'static str,
+            /// The symbol that was bound, which is either `symbol` or one of its fallbacks, or
+            /// `None` if the function could not be loaded.
+            pub bound: Option<&'static str>,
+        }}
+
+        /// The result of `load_with_report`.
+        #[allow(dead_code)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct LoadReport {{
+            /// Every function of the bindings.
+            pub functions: Vec<FnReport>,
+            /// Every version and extension of the bindings.
+            pub features: Vec<(Feature, Availability)>,
+        }}
+
+        #[allow(dead_code)]
+        impl LoadReport {{
+            /// Returns the symbols of the functions that could not be loaded.
+            pub fn missing(&self) -> Vec<&'static str> {{
+                self.functions.iter().filter(|f| f.bound.is_none()).map(|f| f.symbol).collect()
+            }}
+
+            /// Returns the functions that were loaded with a fallback, and the symbol of the fallback.
+            pub fn fallbacks(&self) -> Vec<(&'static str, &'static str)> {{
+                self.functions
+                    .iter()
+                    .filter_map(|f| match f.bound {{
+                        Some(bound) if bound != f.symbol => Some((f.symbol, bound)),
+                        _ => None,
+                    }})
+                    .collect()
+            }}
+
+            /// Returns how much of `feature` could be loaded, or `None` if it is not part of the
+            /// bindings.
+            pub fn availability(&self, feature: Feature) -> Option<Availability> {{
+                self.features.iter().find(|&&(f, _)| f == feature).map(|&(_, a)| a)
+            }}
+        }}
+
+        /// For each function: its symbol, its fallbacks, and the features that provide it.
+        const LOAD_REPORT_FNS: &[(&str, &[&str], &[Feature])] = &["#
+    )?;
+    for cmd in &registry.cmds {
+        let fallbacks = match registry.aliases.get(&cmd.proto.ident) {
+            Some(fbs) => fbs
+                .iter()
+                .map(|name| format!("\"{}\"", gen_symbol_name(registry.api, name)))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => String::new(),
+        };
+        writeln!(
+            dest,
+            "(\"{}\", &[{}], &[{}]),",
+            gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks,
+            gen_features(cmd.introduced_in, &cmd.extensions),
+        )?;
+    }
+    writeln!(dest, "];\n\nconst LOAD_REPORT_FEATURES: &[Feature] = &[")?;
+    for &(major, minor) in &versions {
+        writeln!(dest, "Feature::Version({}, {}),", major, minor)?;
+    }
+    for ext in &registry.extensions {
+        writeln!(dest, "Feature::Extension(\"{}\"),", ext)?;
+    }
+    writeln!(
+        dest,
+        r#"];
+
+        /// Builds a `LoadReport` from the symbols that could be loaded.
+        #[allow(dead_code)]
+        fn build_load_report(loaded: &[&'static str]) -> LoadReport {{
+            use std::collections::HashSet;
+
+            let loaded: HashSet<&str> = loaded.iter().cloned().collect();
+            let mut counts: Vec<(Feature, usize, usize)> =
+                LOAD_REPORT_FEATURES.iter().map(|&f| (f, 0, 0)).collect();
+            let mut functions = Vec::with_capacity(LOAD_REPORT_FNS.len());
+
+            for &(symbol, fallbacks, features) in LOAD_REPORT_FNS {{
+                let bound = Some(symbol)
+                    .into_iter()
+                    .chain(fallbacks.iter().cloned())
+                    .find(|s| loaded.contains(s));
+                for feature in features {{
+                    if let Some(count) = counts.iter_mut().find(|c| c.0 == *feature) {{
+                        count.1 += 1;
+                        if bound.is_some() {{
+                            count.2 += 1;
+                        }}
+                    }}
+                }}
+                functions.push(FnReport {{ symbol: symbol, bound: bound }});
+            }}
+
+            LoadReport {{
+                functions: functions,
+                features: counts
+                    .into_iter()
+                    .map(|(feature, total, loaded)| {{
+                        let availability = if loaded == total {{
+                            Availability::Full
+                        }} else if loaded == 0 {{
+                            Availability::Unavailable
+                        }} else {{
+                            Availability::Partial
+                        }};
+                        (feature, availability)
+                    }})
+                    .collect(),
+            }}
+        }}"#
+    )
+}
+
+/// Generates the `ContextInfo` type, if `options.context_info` is set and the bindings can query
+/// the version of the context.
+///
+/// `ContextInfo::query` calls the functions of the global generator, or the methods of the
+/// `struct_name` structure if there is one. `get_string_i_loaded` is the expression that returns
+/// whether `glGetStringi` was loaded in these bindings, such as `GetStringi::is_loaded()`.
+pub fn gen_context_info<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    struct_name: Option<&str>,
+    get_string_i_loaded: &str,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !options.context_info {
+        return Ok(());
+    }
+
+    let is_es = match registry.api {
+        Api::Gl | Api::GlCore => false,
+        Api::Gles1 | Api::Gles2 | Api::Glsc2 => true,
+        Api::Glx | Api::Wgl | Api::Egl => return Ok(()),
+    };
+    let find_cmd = |ident: &str| registry.cmds.iter().find(|cmd| cmd.proto.ident == ident);
+    let get_string = match find_cmd("GetString") {
+        Some(cmd) => cmd,
+        _ => return Ok(()),
+    };
+    let get_string_i = match (find_cmd("GetStringi"), find_cmd("GetIntegerv")) {
+        (Some(get_string_i), Some(get_integer_v))
+            if registry.enums.iter().any(|e| e.ident == "NUM_EXTENSIONS") =>
+        {
+            Some((get_string_i, get_integer_v))
+        },
+        _ => None,
+    };
+    // the enum arguments are newtypes with `options.typed_groups`
+    let arg = |cmd: &Cmd, index: usize, value: &str| {
+        let ty = &cmd.params[index].ty;
+        if ty.starts_with("groups::") {
+            format!("{}({})", ty, value)
+        } else {
+            value.to_string()
+        }
+    };
+    let (query_params, call_prefix) = match struct_name {
+        Some(name) => (format!("gl: &{}", name), "gl."),
+        None => (String::new(), ""),
+    };
+
+    writeln!(
+        dest,
+        r#"
+        /// The version and the extensions of a context, as reported by the driver.
+        #[allow(dead_code, non_snake_case)]
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub struct ContextInfo {{
+            /// The version of the context, such as `(4, 6)`.
+            pub version: (u8, u8),
+            /// `true` for an OpenGL ES context.
+            pub is_es: bool,
+            /// The version string returned by the driver.
+            pub version_string: String,
+            /// All the extensions supported by the context, including the ones that are not part
+            /// of the bindings.
+            pub extensions: Vec<String>,"#
+    )?;
+    for ext in &registry.extensions {
+        writeln!(
+            dest,
+            "/// `true` if the context supports `{ext}`.\npub {ext}: bool,",
+            ext = ext
+        )?;
+    }
+    writeln!(
+        dest,
+        r#"}}
+
+        #[allow(dead_code)]
+        impl ContextInfo {{
+            /// Builds a `ContextInfo` from a version string, such as `"4.6.0 NVIDIA 390.12"` or
+            /// `"OpenGL ES 3.2 Mesa 20.0.8"`, and a list of extensions.
+            pub fn new<'a, I>(version_string: &str, extensions: I) -> ContextInfo
+                where I: IntoIterator<Item = &'a str>
+            {{
+                let extensions: Vec<String> = extensions.into_iter().map(|e| e.to_string()).collect();
+                let (is_es, version) = ContextInfo::parse_version(version_string).unwrap_or((false, (0, 0)));
+                ContextInfo {{"#
+    )?;
+    for ext in &registry.extensions {
+        writeln!(
+            dest,
+            "{ext}: extensions.iter().any(|e| e == \"{ext}\"),",
+            ext = ext
+        )?;
+    }
+    writeln!(
+        dest,
+        r#"
+                    version: version,
+                    is_es: is_es,
+                    version_string: version_string.to_string(),
+                    extensions: extensions,
+                }}
+            }}
+
+            /// Parses a version string, and returns whether it is an OpenGL ES version, and the
+            /// major and minor version numbers.
+            pub fn parse_version(version_string: &str) -> Option<(bool, (u8, u8))> {{
+                let mut is_es = false;
+                let mut rest = version_string.trim();
+                for prefix in &["OpenGL ES-CM ", "OpenGL ES-CL ", "OpenGL ES "] {{
+                    if rest.starts_with(prefix) {{
+                        is_es = true;
+                        rest = &rest[prefix.len()..];
+                        break;
+                    }}
+                }}
+                let number = rest.split(' ').next().unwrap_or("");
+                let mut parts = number.split('.');
+                let major = parts.next().and_then(|p| p.parse().ok());
+                let minor = parts.next().and_then(|p| p.parse().ok());
+                match (major, minor) {{
+                    (Some(major), Some(minor)) => Some((is_es, (major, minor))),
+                    _ => None,
+                }}
+            }}
+
+            /// Returns `true` if the context is an {api_name} context of version `major.minor` or
+            /// later.
+            pub fn supports_version(&self, major: u8, minor: u8) -> bool {{
+                self.is_es == {is_es} && self.version >= (major, minor)
+            }}
+
+            /// Returns `true` if the context supports the extension, which doesn't need to be part
+            /// of the bindings.
+            pub fn supports_extension(&self, extension: &str) -> bool {{
+                self.extensions.iter().any(|e| e == extension)
+            }}
+
+            /// Queries the version and the extensions of the current context.
+            pub unsafe fn query({query_params}) -> ContextInfo {{
+                use std::ffi::CStr;
+
+                unsafe fn to_str(ptr: *const types::GLubyte) -> String {{
+                    if ptr.is_null() {{
+                        String::new()
+                    }} else {{
+                        CStr::from_ptr(ptr as *const _).to_string_lossy().into_owned()
+                    }}
+                }}
+
+                let version_string = to_str({p}GetString({version_arg}));"#,
+        api_name = if is_es { "OpenGL ES" } else { "OpenGL" },
+        is_es = is_es,
+        query_params = query_params,
+        p = call_prefix,
+        version_arg = arg(get_string, 0, "VERSION"),
+    )?;
+    if let Some((get_string_i, get_integer_v)) = get_string_i {
+        writeln!(
+            dest,
+            r#"
+                // `glGetString(GL_EXTENSIONS)` is not available in core profiles
+                let (_, version) = ContextInfo::parse_version(&version_string).unwrap_or((false, (0, 0)));
+                if version >= (3, 0) && {is_loaded} {{
+                    let mut count = 0;
+                    {p}GetIntegerv({count_arg}, &mut count);
+                    let extensions: Vec<String> = (0..count)
+                        .map(|i| to_str({p}GetStringi({name_arg}, i as types::GLuint)))
+                        .collect();
+                    return ContextInfo::new(&version_string, extensions.iter().map(|e| &e[..]));
+                }}"#,
+            is_loaded = get_string_i_loaded,
+            p = call_prefix,
+            count_arg = arg(get_integer_v, 0, "NUM_EXTENSIONS"),
+            name_arg = arg(get_string_i, 0, "EXTENSIONS"),
+        )?;
+    }
+    writeln!(
+        dest,
+        r#"
+                let extensions = to_str({p}GetString({extensions_arg}));
+                ContextInfo::new(&version_string, extensions.split_whitespace())
+            }}
+        }}"#,
+        p = call_prefix,
+        extensions_arg = arg(get_string, 0, "EXTENSIONS"),
+    )
+}
+
+/// Parses the value of an enum, which is either decimal or hexadecimal.
+fn parse_enum_value(value: &str) -> Option<u64> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Returns the names of the types declared by a file of `templates/types`.
+fn template_types(template: &str) -> Vec<&str> {
+    template
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some("pub"), Some("type"), Some(name))
+                | (Some("pub"), Some("enum"), Some(name))
+                | (Some("pub"), Some("struct"), Some(name)) => {
+                    Some(name.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_')))
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Generates all the type aliases for a namespace.
+///
+/// Aliases are either `pub type = ...` or `#[repr(C)] pub struct ... { ... }`. They come from
+/// the `<types>` section of the registry, except for the things that we can't obtain from the
+/// XML files, which are kept in `templates/types`. A type declared by a template replaces the
+/// one in the registry, and the types that the registry only names and no template declares
+/// are declared as opaque, so that the bindings still compile.
+pub fn gen_types<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    let templates: &[&str] = match registry.api {
+        Api::Glx => &[
+            include_str!("templates/types/gl.rs"),
+            include_str!("templates/types/glx.rs"),
+        ],
+        Api::Wgl => &[
+            include_str!("templates/types/gl.rs"),
+            include_str!("templates/types/wgl.rs"),
+        ],
+        Api::Egl => &[include_str!("templates/types/egl.rs")],
+        _ => &[include_str!("templates/types/gl.rs")],
+    };
+    let mut declared = Vec::new();
+    for template in templates {
+        writeln!(dest, "{}", template)?;
+        declared.extend(template_types(template));
+    }
+    let required: Vec<&str> = registry
+        .types
+        .values()
+        .filter_map(|ty| ty.requires.as_deref())
+        .collect();
+
+    for ty in registry.types.values() {
+        let item = match ty.def {
+            // the templates take precedence
+            _ if declared.contains(&&*ty.ident) => continue,
+            // C source, like an `#include`, has no Rust counterpart
+            TypeDef::Source(_) => continue,
+            // a named type that others require, like `inttypes`, stands for a header
+            TypeDef::External if required.contains(&&*ty.ident) => continue,
+
+            TypeDef::External | TypeDef::Opaque => format!("pub enum {} {{}}", ty.ident),
+            TypeDef::Alias(ref alias) => format!("pub type {} = {};", ty.ident, alias),
+            TypeDef::Struct(ref fields) => format!(
+                "#[repr(C)]\npub struct {} {{\n{}}}",
+                ty.ident,
+                fields
+                    .iter()
+                    .map(|field| format!("    pub {}: {},\n", field.ident, field.ty))
+                    .collect::<String>(),
+            ),
+            TypeDef::FnPtr {
+                abi,
+                ref params,
+                ref ret,
+            } => format!(
+                "pub type {} = Option<extern \"{}\" fn({}){}>;",
+                ty.ident,
+                abi,
+                params
+                    .iter()
+                    .map(|param| format!("{}: {}", param.ident, param.ty))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                match &**ret {
+                    "()" => String::new(),
+                    ret => format!(" -> {}", ret),
+                },
+            ),
+        };
+
+        if let Some(ref comment) = ty.comment {
+            writeln!(dest, "/// {}", comment)?;
+        }
+        writeln!(dest, "{}", item)?;
     }
 
     Ok(())
@@ -14,6 +14,7 @@
 
 use registry::Registry;
 use std::io;
+use GeneratorOptions;
 
 #[allow(missing_copy_implementations)]
 pub struct StaticGenerator;
@@ -23,10 +24,25 @@ impl super::Generator for StaticGenerator {
     where
         W: io::Write,
     {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
         write_header(dest)?;
         write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
-        write_fns(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
         Ok(())
     }
 }
@@ -63,7 +79,7 @@ where
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry, dest)?;
 
     writeln!(
         dest,
@@ -74,11 +90,12 @@ where
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
         super::gen_enum_item(enm, "types::", dest)?;
     }
 
@@ -88,7 +105,7 @@ where
 /// io::Writes all functions corresponding to the GL bindings.
 ///
 /// These are foreign functions, they don't have any content.
-fn write_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_fns<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -100,6 +117,8 @@ where
     )?;
 
     for cmd in &registry.cmds {
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
         writeln!(
             dest,
             "#[link_name=\"{symbol}\"]
@@ -14,6 +14,7 @@
 
 use registry::Registry;
 use std::io;
+use GeneratorOptions;
 
 #[allow(missing_copy_implementations)]
 pub struct StaticStructGenerator;
@@ -23,11 +24,26 @@ impl super::Generator for StaticStructGenerator {
     where
         W: io::Write,
     {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
         write_header(dest)?;
         write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
         write_struct(registry, dest)?;
-        write_impl(registry, dest)?;
+        write_impl(registry, options, dest)?;
         write_fns(registry, dest)?;
         Ok(())
     }
@@ -65,17 +81,18 @@ where
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry, dest)?;
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
         super::gen_enum_item(enm, "types::", dest)?;
     }
 
@@ -100,7 +117,7 @@ where
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_impl<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -115,6 +132,8 @@ where
     )?;
 
     for cmd in &registry.cmds {
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
         writeln!(
             dest,
             "#[allow(non_snake_case)]
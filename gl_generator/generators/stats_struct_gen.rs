@@ -0,0 +1,632 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+use GeneratorOptions;
+
+/// Like `StructGenerator`, but each command counts its calls in a `Stats` table, which can be
+/// read with `Gl::stats`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatsStructGenerator {
+    /// Also measure the time spent in each command with `Instant`.
+    pub timing: bool,
+    /// Also count the calls of each place that calls a command, with `#[track_caller]`.
+    ///
+    /// The call sites are counted in a map behind a lock, which is much slower than the counters
+    /// of the commands.
+    pub call_sites: bool,
+}
+
+impl super::Generator for StatsStructGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(self, dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        super::gen_command_ids(registry, dest)?;
+        write_stats(registry, self, dest)?;
+        write_snapshot(dest)?;
+        write_fnptr_struct_def(dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        write_struct(registry, dest)?;
+        write_impl(registry, self, options, dest)?;
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(generator: &StatsStructGenerator, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            pub use std::mem;
+            pub use std::marker::Send;
+            pub use std::os::raw;
+            pub use std::panic::Location;
+            pub use std::sync::atomic::{{AtomicU64, Ordering}};
+            pub use std::sync::Arc;
+            pub use std::time::Duration;"#
+    )?;
+    if generator.timing {
+        writeln!(dest, "pub use std::time::Instant;")?;
+    }
+    if generator.call_sites {
+        writeln!(
+            dest,
+            "pub use std::collections::HashMap;
+            pub use std::sync::{{Mutex, PoisonError}};"
+        )?;
+    }
+    writeln!(dest, "}}")
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `Stats` structure, which holds a counter per command, and the timers and the call
+/// sites if the generator records them.
+fn write_stats<W>(
+    registry: &Registry,
+    generator: &StatsStructGenerator,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// The statistics of the calls made through a `Gl` struct, since it was loaded or since
+        /// the last reset.
+        pub struct Stats {{
+            /// The number of calls of each command.
+            counts: [__gl_imports::AtomicU64; {len}],",
+        len = registry.cmds.len()
+    )?;
+    if generator.timing {
+        writeln!(
+            dest,
+            "/// The nanoseconds spent in each command.
+            nanos: [__gl_imports::AtomicU64; {len}],",
+            len = registry.cmds.len()
+        )?;
+    }
+    if generator.call_sites {
+        writeln!(
+            dest,
+            "/// The number of calls of each command from each place.
+            call_sites: __gl_imports::Mutex<__gl_imports::HashMap<(CommandId, &'static __gl_imports::Location<'static>), u64>>,"
+        )?;
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        #[allow(dead_code)]
+        impl Stats {{
+            #[allow(clippy::declare_interior_mutable_const)]
+            fn new() -> Stats {{
+                const ZERO: __gl_imports::AtomicU64 = __gl_imports::AtomicU64::new(0);
+                Stats {{
+                    counts: [ZERO; {len}],",
+        len = registry.cmds.len()
+    )?;
+    if generator.timing {
+        writeln!(dest, "nanos: [ZERO; {len}],", len = registry.cmds.len())?;
+    }
+    if generator.call_sites {
+        writeln!(
+            dest,
+            "call_sites: __gl_imports::Mutex::new(__gl_imports::HashMap::new()),"
+        )?;
+    }
+    writeln!(
+        dest,
+        "}}
+            }}
+
+            #[inline]
+            fn record(&self, cmd: CommandId) {{
+                self.counts[cmd as usize].fetch_add(1, __gl_imports::Ordering::Relaxed);
+            }}
+
+            /// Returns the current statistics.
+            pub fn snapshot(&self) -> StatsSnapshot {{
+                self.collect(false)
+            }}
+
+            /// Returns the current statistics, and sets them back to zero at the same time, such
+            /// as at the end of a frame.
+            pub fn take(&self) -> StatsSnapshot {{
+                self.collect(true)
+            }}
+
+            /// Sets the statistics back to zero.
+            pub fn reset(&self) {{
+                self.collect(true);
+            }}
+
+            fn collect(&self, reset: bool) -> StatsSnapshot {{
+                let read = |value: &__gl_imports::AtomicU64| {{
+                    if reset {{
+                        value.swap(0, __gl_imports::Ordering::Relaxed)
+                    }} else {{
+                        value.load(__gl_imports::Ordering::Relaxed)
+                    }}
+                }};
+                let commands = CommandId::ALL
+                    .iter()
+                    .map(|&cmd| CommandStats {{
+                        cmd: cmd,
+                        count: read(&self.counts[cmd as usize]),
+                        time: {time},
+                    }})
+                    .filter(|stats| stats.count > 0)
+                    .collect();
+                StatsSnapshot {{
+                    commands: commands,
+                    call_sites: self.collect_call_sites(reset),
+                }}
+            }}",
+        time = if generator.timing {
+            "__gl_imports::Duration::from_nanos(read(&self.nanos[cmd as usize]))"
+        } else {
+            "__gl_imports::Duration::default()"
+        }
+    )?;
+
+    if generator.timing {
+        writeln!(
+            dest,
+            "
+            #[inline]
+            fn record_time(&self, cmd: CommandId, start: __gl_imports::Instant) {{
+                let nanos = start.elapsed().as_nanos() as u64;
+                self.nanos[cmd as usize].fetch_add(nanos, __gl_imports::Ordering::Relaxed);
+            }}"
+        )?;
+    }
+
+    if generator.call_sites {
+        writeln!(
+            dest,
+            "
+            #[inline(never)]
+            fn record_call_site(&self, cmd: CommandId, location: &'static __gl_imports::Location<'static>) {{
+                let mut call_sites = self.call_sites.lock().unwrap_or_else(__gl_imports::PoisonError::into_inner);
+                *call_sites.entry((cmd, location)).or_insert(0) += 1;
+            }}
+
+            fn collect_call_sites(&self, reset: bool) -> Vec<CallSiteStats> {{
+                let mut call_sites = self.call_sites.lock().unwrap_or_else(__gl_imports::PoisonError::into_inner);
+                let mut stats = call_sites
+                    .iter()
+                    .map(|(&(cmd, location), &count)| CallSiteStats {{
+                        cmd: cmd,
+                        location: location,
+                        count: count,
+                    }})
+                    .collect::<Vec<_>>();
+                if reset {{
+                    call_sites.clear();
+                }}
+                stats.sort_by(|a, b| {{
+                    b.count
+                        .cmp(&a.count)
+                        .then(a.cmd.cmp(&b.cmd))
+                        .then(a.location.cmp(b.location))
+                }});
+                stats
+            }}"
+        )?;
+    } else {
+        writeln!(
+            dest,
+            "
+            fn collect_call_sites(&self, _reset: bool) -> Vec<CallSiteStats> {{
+                Vec::new()
+            }}"
+        )?;
+    }
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `StatsSnapshot` structure returned by `Stats::snapshot`, and the report it formats.
+fn write_snapshot<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// The statistics of one command.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct CommandStats {{
+            /// The command.
+            pub cmd: CommandId,
+            /// The number of calls.
+            pub count: u64,
+            /// The total time spent in the command, or zero if the bindings don't measure it.
+            pub time: __gl_imports::Duration,
+        }}
+
+        /// The number of calls of one command from one place.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct CallSiteStats {{
+            /// The command.
+            pub cmd: CommandId,
+            /// The place that called the command.
+            pub location: &'static __gl_imports::Location<'static>,
+            /// The number of calls.
+            pub count: u64,
+        }}
+
+        /// How `StatsSnapshot::report` sorts the commands.
+        #[allow(dead_code)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum SortBy {{
+            /// The most called commands first.
+            Count,
+            /// The commands that took the most time first.
+            Time,
+        }}
+
+        /// The statistics returned by `Stats::snapshot` and `Stats::take`.
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
+        pub struct StatsSnapshot {{
+            /// The commands that were called at least once, in the order of `CommandId`.
+            pub commands: Vec<CommandStats>,
+            /// The places that called a command, the hottest first, or nothing if the bindings
+            /// don't count them.
+            pub call_sites: Vec<CallSiteStats>,
+        }}
+
+        #[allow(dead_code)]
+        impl StatsSnapshot {{
+            /// Returns the total number of calls.
+            pub fn total_count(&self) -> u64 {{
+                self.commands.iter().map(|stats| stats.count).sum()
+            }}
+
+            /// Returns the total time spent in the commands.
+            pub fn total_time(&self) -> __gl_imports::Duration {{
+                self.commands.iter().fold(__gl_imports::Duration::default(), |total, stats| total + stats.time)
+            }}
+
+            /// Returns the statistics of `cmd`, if it was called.
+            pub fn get(&self, cmd: CommandId) -> Option<&CommandStats> {{
+                self.commands.iter().find(|stats| stats.cmd == cmd)
+            }}
+
+            /// Returns the commands, sorted with `sort_by`.
+            pub fn sorted(&self, sort_by: SortBy) -> Vec<CommandStats> {{
+                let mut commands = self.commands.clone();
+                match sort_by {{
+                    SortBy::Count => commands.sort_by(|a, b| b.count.cmp(&a.count).then(a.cmd.cmp(&b.cmd))),
+                    SortBy::Time => commands.sort_by(|a, b| b.time.cmp(&a.time).then(a.cmd.cmp(&b.cmd))),
+                }}
+                commands
+            }}
+
+            /// Formats a table with the number of calls and the time of each command, sorted
+            /// with `sort_by`, followed by the ten hottest call sites.
+            pub fn report(&self, sort_by: SortBy) -> String {{
+                let mut report = format!(\"{{:<40}} {{:>10}} {{:>12}}\\n\", \"command\", \"calls\", \"time (ms)\");
+                for stats in self.sorted(sort_by) {{
+                    report.push_str(&format!(
+                        \"{{:<40}} {{:>10}} {{:>12.3}}\\n\",
+                        stats.cmd.name(),
+                        stats.count,
+                        stats.time.as_secs_f64() * 1000.0
+                    ));
+                }}
+                report.push_str(&format!(
+                    \"{{:<40}} {{:>10}} {{:>12.3}}\\n\",
+                    \"total\",
+                    self.total_count(),
+                    self.total_time().as_secs_f64() * 1000.0
+                ));
+                if !self.call_sites.is_empty() {{
+                    report.push_str(&format!(\"\\n{{:<40}} {{:>10}}\\n\", \"call site\", \"calls\"));
+                    for stats in self.call_sites.iter().take(10) {{
+                        report.push_str(&format!(
+                            \"{{:<40}} {{:>10}}\\n\",
+                            format!(\"{{}} ({{}})\", stats.location, stats.cmd.name()),
+                            stats.count
+                        ));
+                    }}
+                }}
+                report
+            }}
+        }}
+    "
+    )
+}
+
+/// Creates a `FnPtr` structure which contains the store for a single binding.
+fn write_fnptr_struct_def<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(dead_code, missing_copy_implementations)]
+        #[derive(Clone)]
+        pub struct FnPtr {{
+            /// The function pointer that will be used when calling the function.
+            f: *const __gl_imports::raw::c_void,
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
+            is_loaded: bool,
+        }}
+
+        impl FnPtr {{
+            /// Creates a `FnPtr` from a load attempt.
+            fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
+                if ptr.is_null() {{
+                    FnPtr {{ f: missing, is_loaded: false }}
+                }} else {{
+                    FnPtr {{ f: ptr, is_loaded: true }}
+                }}
+            }}
+
+            /// Returns `true` if the function has been successfully loaded.
+            ///
+            /// If it returns `false`, calling the corresponding function will fail.
+            #[inline]
+            #[allow(dead_code)]
+            pub fn is_loaded(&self) -> bool {{
+                self.is_loaded
+            }}
+        }}
+    "
+    )
+}
+
+/// Creates a structure which stores all the `FnPtr` of the bindings, and the statistics.
+///
+/// The name of the struct corresponds to the namespace.
+fn write_struct<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        #[allow(non_camel_case_types, non_snake_case, dead_code)]
+        #[derive(Clone)]
+        pub struct {api} {{",
+        api = super::gen_struct_name(registry.api)
+    )?;
+
+    for cmd in &registry.cmds {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        writeln!(dest, "pub {name}: FnPtr,", name = cmd.proto.ident)?;
+    }
+    writeln!(
+        dest,
+        "/// The statistics of the calls, shared by the clones of the struct.
+        _stats: __gl_imports::Arc<Stats>,"
+    )?;
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(dest, "}}")
+}
+
+/// Creates the `impl` of the structure created by `write_struct`.
+fn write_impl<W>(
+    registry: &Registry,
+    generator: &StatsStructGenerator,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol using a custom load function. This allows for the
+            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+            ///
+            /// ~~~ignore
+            /// let gl = Gl::load_with(|s| glfw.get_proc_address(s));
+            /// ~~~
+            #[allow(dead_code, unused_variables)]
+            pub fn load_with<F>(mut loadfn: F) -> {api} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                #[inline(never)]
+                fn do_metaloadfn(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void,
+                                 symbol: &'static str,
+                                 symbols: &[&'static str])
+                                 -> *const __gl_imports::raw::c_void {{
+                    let mut ptr = loadfn(symbol);
+                    if ptr.is_null() {{
+                        for &sym in symbols {{
+                            ptr = loadfn(sym);
+                            if !ptr.is_null() {{ break; }}
+                        }}
+                    }}
+                    ptr
+                }}
+                let mut metaloadfn = |symbol: &'static str, symbols: &[&'static str]| {{
+                    do_metaloadfn(&mut loadfn, symbol, symbols)
+                }};
+                {api} {{",
+                  api = super::gen_struct_name(registry.api))?;
+
+    for cmd in &registry.cmds {
+        writeln!(
+            dest,
+            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}]), \
+                missing_fns::{name} as *const __gl_imports::raw::c_void),",
+            name = cmd.proto.ident,
+            symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
+            fallbacks = match registry.aliases.get(&cmd.proto.ident) {
+                Some(fbs) => fbs
+                    .iter()
+                    .map(|name| format!("\"{}\"", super::gen_symbol_name(registry.api, name)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                None => String::new(),
+            },
+        )?
+    }
+    writeln!(dest, "_stats: __gl_imports::Arc::new(Stats::new()),")?;
+    writeln!(dest, "_priv: ()")?;
+
+    writeln!(
+        dest,
+        "}}
+        }}
+
+        /// Returns the statistics of the calls made through this struct and its clones.
+        #[allow(dead_code)]
+        pub fn stats(&self) -> &Stats {{
+            &self._stats
+        }}"
+    )?;
+
+    // the call sites are recorded with `#[track_caller]`, which may already be needed by
+    // `options.missing_fn`
+    let attr = if generator.call_sites {
+        "#[track_caller]"
+    } else {
+        super::gen_missing_fn_attr(options)
+    };
+    for cmd in &registry.cmds {
+        let check =
+            super::gen_missing_fn_check(cmd, options, &format!("self.{}.f", cmd.proto.ident));
+        let call = format!(
+            "__gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                (fn_ptr)({idents})",
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+        );
+        let call_site = if generator.call_sites {
+            format!(
+                "self._stats.record_call_site(CommandId::{}, __gl_imports::Location::caller());",
+                cmd.proto.ident
+            )
+        } else {
+            String::new()
+        };
+        let call = if generator.timing && cmd.proto.ty == "()" {
+            format!(
+                "let start_time = __gl_imports::Instant::now();
+                {call};
+                self._stats.record_time(CommandId::{name}, start_time);",
+                call = call,
+                name = cmd.proto.ident
+            )
+        } else if generator.timing {
+            format!(
+                "let start_time = __gl_imports::Instant::now();
+                let r = {call};
+                self._stats.record_time(CommandId::{name}, start_time);
+                r",
+                call = call,
+                name = cmd.proto.ident
+            )
+        } else {
+            call
+        };
+
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+        writeln!(
+            dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)]
+            #[inline] {attr} pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{
+                self._stats.record(CommandId::{name});
+                {call_site}
+                {check}
+                {call}
+            }}",
+            attr = attr,
+            name = cmd.proto.ident,
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            call_site = call_site,
+            check = check,
+            call = call,
+        )?
+    }
+
+    writeln!(
+        dest,
+        "}}
+
+        unsafe impl __gl_imports::Send for {api} {{}}",
+        api = super::gen_struct_name(registry.api)
+    )
+}
@@ -14,6 +14,7 @@
 
 use registry::Registry;
 use std::io;
+use GeneratorOptions;
 
 #[allow(missing_copy_implementations)]
 pub struct StructGenerator;
@@ -23,13 +24,37 @@ impl super::Generator for StructGenerator {
     where
         W: io::Write,
     {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
         write_header(dest)?;
         write_type_aliases(registry, dest)?;
-        write_enums(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
         write_fnptr_struct_def(dest)?;
-        write_panicking_fns(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
         write_struct(registry, dest)?;
-        write_impl(registry, dest)?;
+        write_impl(registry, options, dest)?;
+        super::gen_load_report(registry, options, dest)?;
+        write_load_with_report_fn(registry, options, dest)?;
+        super::gen_context_info(
+            registry,
+            options,
+            Some(super::gen_struct_name(registry.api)),
+            "gl.GetStringi.is_loaded()",
+            dest,
+        )?;
         Ok(())
     }
 }
@@ -67,17 +92,18 @@ where
     "#
     )?;
 
-    super::gen_types(registry.api, dest)?;
+    super::gen_types(registry, dest)?;
 
     writeln!(dest, "}}")
 }
 
 /// Creates all the `<enum>` elements at the root of the bindings.
-fn write_enums<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
     for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
         super::gen_enum_item(enm, "types::", dest)?;
     }
 
@@ -97,18 +123,16 @@ where
         pub struct FnPtr {{
             /// The function pointer that will be used when calling the function.
             f: *const __gl_imports::raw::c_void,
-            /// True if the pointer points to a real function, false if points to a `panic!` fn.
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
             is_loaded: bool,
         }}
 
         impl FnPtr {{
             /// Creates a `FnPtr` from a load attempt.
-            fn new(ptr: *const __gl_imports::raw::c_void) -> FnPtr {{
+            fn new(ptr: *const __gl_imports::raw::c_void, missing: *const __gl_imports::raw::c_void) -> FnPtr {{
                 if ptr.is_null() {{
-                    FnPtr {{
-                        f: missing_fn_panic as *const __gl_imports::raw::c_void,
-                        is_loaded: false
-                    }}
+                    FnPtr {{ f: missing, is_loaded: false }}
                 }} else {{
                     FnPtr {{ f: ptr, is_loaded: true }}
                 }}
@@ -127,23 +151,6 @@ where
     )
 }
 
-/// Creates a `panicking` module which contains one function per GL command.
-///
-/// These functions are the mocks that are called if the real function could not be loaded.
-fn write_panicking_fns<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
-where
-    W: io::Write,
-{
-    writeln!(
-        dest,
-        "#[inline(never)]
-        fn missing_fn_panic() -> ! {{
-            panic!(\"{api} function was not loaded\")
-        }}",
-        api = registry.api
-    )
-}
-
 /// Creates a structure which stores all the `FnPtr` of the bindings.
 ///
 /// The name of the struct corresponds to the namespace.
@@ -172,7 +179,7 @@ where
 }
 
 /// Creates the `impl` of the structure created by `write_struct`.
-fn write_impl<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+fn write_impl<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
 where
     W: io::Write,
 {
@@ -209,7 +216,8 @@ where
     for cmd in &registry.cmds {
         writeln!(
             dest,
-            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}])),",
+            "{name}: FnPtr::new(metaloadfn(\"{symbol}\", &[{fallbacks}]), \
+                missing_fns::{name} as *const __gl_imports::raw::c_void),",
             name = cmd.proto.ident,
             symbol = super::gen_symbol_name(registry.api, &cmd.proto.ident),
             fallbacks = match registry.aliases.get(&cmd.proto.ident) {
@@ -232,14 +240,22 @@ where
     )?;
 
     for cmd in &registry.cmds {
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+        let check =
+            super::gen_missing_fn_check(cmd, options, &format!("self.{}.f", cmd.proto.ident));
         writeln!(dest,
             "#[allow(non_snake_case, unused_variables, dead_code)]
-            #[inline] pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
-                __gl_imports::mem::transmute::<_, extern \"system\" fn({typed_params}) -> {return_suffix}>\
-                    (self.{name}.f)({idents}) \
+            #[inline] {missing_fn_attr} pub unsafe fn {name}(&self, {params}) -> {return_suffix} {{ \
+                {check}
+                __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents}) \
             }}",
             name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
             params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
             typed_params = super::gen_parameters(cmd, false, true).join(", "),
             return_suffix = cmd.proto.ty,
             idents = super::gen_parameters(cmd, true, false).join(", "),
@@ -254,3 +270,42 @@ where
         api = super::gen_struct_name(registry.api)
     )
 }
+
+/// Creates the `load_with_report` function of the structure, if `options.load_report` is set.
+///
+/// See also `generators::gen_load_report`.
+fn write_load_with_report_fn<W>(
+    registry: &Registry,
+    options: &GeneratorOptions,
+    dest: &mut W,
+) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !options.load_report {
+        return Ok(());
+    }
+
+    writeln!(dest,
+                  "impl {api} {{
+            /// Load each OpenGL symbol like `load_with`, and report which functions could be
+            /// loaded.
+            ///
+            /// ~~~ignore
+            /// let (gl, report) = Gl::load_with_report(|s| glfw.get_proc_address(s));
+            /// ~~~
+            #[allow(dead_code)]
+            pub fn load_with_report<F>(mut loadfn: F) -> ({api}, LoadReport) where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                let mut loaded = Vec::new();
+                let gl = {api}::load_with(|symbol| {{
+                    let ptr = loadfn(symbol);
+                    if !ptr.is_null() {{
+                        loaded.push(symbol);
+                    }}
+                    ptr
+                }});
+                (gl, build_load_report(&loaded))
+            }}
+        }}",
+                  api = super::gen_struct_name(registry.api))
+}
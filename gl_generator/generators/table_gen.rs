@@ -0,0 +1,339 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+use GeneratorOptions;
+
+/// Like `GlobalGenerator`, but the function pointers are stored in one table, and are loaded
+/// with a single loop over a table of symbol names.
+///
+/// The generated code is much smaller, which makes it faster to compile.
+#[allow(missing_copy_implementations)]
+pub struct TableGenerator;
+
+impl super::Generator for TableGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
+        write_symbols(registry, dest)?;
+        write_storage(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        super::gen_missing_fn_ptrs(registry, dest)?;
+        write_is_loaded_fn(dest)?;
+        write_load_fn(dest)?;
+        super::gen_load_report(registry, options, dest)?;
+        write_load_with_report_fn(options, dest)?;
+        super::gen_context_info(
+            registry,
+            options,
+            None,
+            &format!(
+                "is_loaded(\"{}\")",
+                super::gen_symbol_name(registry.api, "GetStringi")
+            ),
+            dest,
+        )?;
+        Ok(())
+    }
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            pub use std::mem;
+            pub use std::os::raw;
+            pub use std::ptr;
+            pub use std::sync::atomic::{{AtomicBool, AtomicPtr, Ordering}};
+        }}
+    "#
+    )
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(
+        dest,
+        "
+        }}
+    "
+    )
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the functions corresponding to the GL commands.
+///
+/// Each function calls the pointer at its index in the table of the `storage` module created by
+///  `write_storage`.
+fn write_fns<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for (index, cmd) in registry.cmds.iter().enumerate() {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+
+        let check = super::gen_missing_fn_check(cmd, options, &format!("storage::get({})", index));
+        writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline] {missing_fn_attr}
+            pub unsafe fn {name}({params}) -> {return_suffix} {{ \
+                {check} \
+                __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents}) \
+            }}",
+            name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `SYMBOLS` string table.
+///
+/// The table has one line per command, in the order of the pointer table. Each line holds the
+///  symbol of the command, followed by its fallbacks, separated by spaces.
+fn write_symbols<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest, "const SYMBOLS: &str = \"\\")?;
+
+    for c in &registry.cmds {
+        let mut symbols = vec![super::gen_symbol_name(registry.api, &c.proto.ident)];
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            symbols.extend(
+                v.iter()
+                    .map(|name| super::gen_symbol_name(registry.api, name)),
+            );
+        }
+        writeln!(dest, "{}\\n\\", symbols.join(" "))?;
+    }
+
+    writeln!(dest, "\";")
+}
+
+/// Creates a `storage` module which contains the table of function pointers, and a table of
+///  flags telling whether each of them was loaded.
+fn write_storage<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        mod storage {{
+            #![allow(clippy::declare_interior_mutable_const)]
+            use super::__gl_imports::{{raw, AtomicBool, AtomicPtr, Ordering}};
+            use super::missing_fns;
+
+            const NOT_LOADED: AtomicBool = AtomicBool::new(false);
+
+            /// The function pointers that will be used when calling the functions.
+            ///
+            /// The function pointers don't guard any other data, so relaxed atomics are enough.
+            static FNS: [AtomicPtr<raw::c_void>; {len}] = [",
+        len = registry.cmds.len()
+    )?;
+
+    for c in &registry.cmds {
+        writeln!(
+            dest,
+            "AtomicPtr::new(missing_fns::{} as *mut raw::c_void),",
+            c.proto.ident
+        )?;
+    }
+
+    writeln!(dest,
+             "];
+            /// True if the pointer points to a real function, false if it points to its stub in
+            /// `missing_fns`.
+            static LOADED: [AtomicBool; {len}] = [NOT_LOADED; {len}];
+
+            /// Stores the result of a load attempt.
+            pub fn set(index: usize, ptr: *const raw::c_void) {{
+                if ptr.is_null() {{
+                    FNS[index].store(super::missing_fn_ptr(index) as *mut raw::c_void, Ordering::Relaxed);
+                    LOADED[index].store(false, Ordering::Relaxed);
+                }} else {{
+                    FNS[index].store(ptr as *mut raw::c_void, Ordering::Relaxed);
+                    LOADED[index].store(true, Ordering::Relaxed);
+                }}
+            }}
+
+            /// Returns the function pointer that will be used when calling the function.
+            #[inline]
+            pub fn get(index: usize) -> *const raw::c_void {{
+                FNS[index].load(Ordering::Relaxed)
+            }}
+
+            /// Returns `true` if the function has been successfully loaded.
+            #[inline]
+            pub fn is_loaded(index: usize) -> bool {{
+                LOADED[index].load(Ordering::Relaxed)
+            }}
+        }}
+    ",
+             len = registry.cmds.len())
+}
+
+/// Creates the `is_loaded` function, which looks up a command by its symbol.
+fn write_is_loaded_fn<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        /// Returns `true` if the function with the given symbol, such as `\"glClear\"`, has been
+        /// successfully loaded.
+        #[allow(dead_code)]
+        pub fn is_loaded(symbol: &str) -> bool {{
+            SYMBOLS
+                .lines()
+                .position(|symbols| symbols.split(' ').next() == Some(symbol))
+                .map_or(false, storage::is_loaded)
+        }}
+    "
+    )
+}
+
+/// Creates the `load_with` function.
+///
+/// The function loops over the `SYMBOLS` table created by `write_symbols`, and stores the first
+///  symbol of each line that could be loaded.
+fn write_load_fn<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "
+        /// Load each OpenGL symbol using a custom load function. This allows for the
+        /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+        /// ~~~ignore
+        /// gl::load_with(|s| glfw.get_proc_address(s));
+        /// ~~~
+        #[allow(dead_code)]
+        pub fn load_with<F>(mut loadfn: F) where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            #[inline(never)]
+            fn inner(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void) {{
+                for (index, symbols) in SYMBOLS.lines().enumerate() {{
+                    let mut ptr = __gl_imports::ptr::null();
+                    for symbol in symbols.split(' ') {{
+                        ptr = loadfn(symbol);
+                        if !ptr.is_null() {{ break; }}
+                    }}
+                    storage::set(index, ptr);
+                }}
+            }}
+
+            inner(&mut loadfn)
+        }}
+    ")
+}
+
+/// Creates the `load_with_report` function, if `options.load_report` is set.
+///
+/// See also `generators::gen_load_report`.
+fn write_load_with_report_fn<W>(options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    if !options.load_report {
+        return Ok(());
+    }
+
+    writeln!(dest,
+                  "
+        /// Load each OpenGL symbol like `load_with`, and report which functions could be loaded.
+        /// ~~~ignore
+        /// let report = gl::load_with_report(|s| glfw.get_proc_address(s));
+        /// ~~~
+        #[allow(dead_code)]
+        pub fn load_with_report<F>(mut loadfn: F) -> LoadReport where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+            let mut loaded = Vec::new();
+            load_with(|symbol| {{
+                let ptr = loadfn(symbol);
+                if !ptr.is_null() {{
+                    loaded.push(symbol);
+                }}
+                ptr
+            }});
+            build_load_report(&loaded)
+        }}
+    ")
+}
@@ -1,3 +1,5 @@
+// Windows types the registry only names
+
 // From WinNT.h
 
 pub type CHAR = super::__gl_imports::raw::c_char;
@@ -5,14 +7,6 @@ pub type HANDLE = PVOID;
 pub type LONG = super::__gl_imports::raw::c_long;
 pub type LPCSTR = *const super::__gl_imports::raw::c_char;
 pub type VOID = ();
-// #define DECLARE_HANDLE(name) struct name##__{int unused;}; typedef struct name##__ *name
-pub type HPBUFFERARB = *const super::__gl_imports::raw::c_void;
-pub type HPBUFFEREXT = *const super::__gl_imports::raw::c_void;
-pub type HVIDEOOUTPUTDEVICENV = *const super::__gl_imports::raw::c_void;
-pub type HPVIDEODEV = *const super::__gl_imports::raw::c_void;
-pub type HPGPUNV = *const super::__gl_imports::raw::c_void;
-pub type HGPUNV = *const super::__gl_imports::raw::c_void;
-pub type HVIDEOINPUTDEVICENV = *const super::__gl_imports::raw::c_void;
 
 // From Windef.h
 
@@ -26,6 +20,7 @@ pub type HGLRC = *const super::__gl_imports::raw::c_void;
 pub type INT = super::__gl_imports::raw::c_int;
 pub type PVOID = *const super::__gl_imports::raw::c_void;
 pub type LPVOID = *const super::__gl_imports::raw::c_void;
+// Must be cast to the actual function pointer type before use
 pub enum __PROC_fn {}
 pub type PROC = *mut __PROC_fn;
 
@@ -125,15 +120,3 @@ pub struct PIXELFORMATDESCRIPTOR {
     pub dwVisibleMask: DWORD,
     pub dwDamageMask: DWORD,
 }
-
-#[repr(C)]
-pub struct _GPU_DEVICE {
-    cb: DWORD,
-    DeviceName: [CHAR; 32],
-    DeviceString: [CHAR; 128],
-    Flags: DWORD,
-    rcVirtualScreen: RECT,
-}
-
-pub struct GPU_DEVICE(_GPU_DEVICE);
-pub struct PGPU_DEVICE(*const _GPU_DEVICE);
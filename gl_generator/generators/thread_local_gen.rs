@@ -0,0 +1,336 @@
+// Copyright 2015 Brendan Zabarauskas and the gl-rs developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use registry::Registry;
+use std::io;
+use GeneratorOptions;
+
+/// Like `GlobalGenerator`, but the functions are loaded into a table per context, such as
+/// `GlContextTable`, and the functions call the table that was made current on the calling
+/// thread with `make_current`.
+///
+/// This is needed when the function pointers are only valid for the context they were loaded
+/// for, as is true on Windows.
+#[allow(missing_copy_implementations)]
+pub struct ThreadLocalGenerator;
+
+impl super::Generator for ThreadLocalGenerator {
+    fn write<W>(&self, registry: &Registry, dest: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_with_options(registry, &GeneratorOptions::default(), dest)
+    }
+
+    fn write_with_options<W>(
+        &self,
+        registry: &Registry,
+        options: &GeneratorOptions,
+        dest: &mut W,
+    ) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let registry = &super::apply_options(registry, options);
+        write_header(dest)?;
+        write_type_aliases(registry, dest)?;
+        write_enums(registry, options, dest)?;
+        super::gen_groups(registry, options, dest)?;
+        super::gen_enum_names(registry, options, dest)?;
+        write_fns(registry, options, dest)?;
+        write_symbols(registry, dest)?;
+        write_table(registry, dest)?;
+        write_current(registry, dest)?;
+        super::gen_missing_fns(registry, options, dest)?;
+        super::gen_missing_fn_ptrs(registry, dest)?;
+        super::gen_context_info(
+            registry,
+            options,
+            None,
+            &format!(
+                "is_loaded(\"{}\")",
+                super::gen_symbol_name(registry.api, "GetStringi")
+            ),
+            dest,
+        )?;
+        Ok(())
+    }
+}
+
+/// Returns the name of the table type, such as `GlContextTable`.
+fn table_name(registry: &Registry) -> String {
+    format!("{}ContextTable", super::gen_struct_name(registry.api))
+}
+
+/// Creates a `__gl_imports` module which contains all the external symbols that we need for the
+///  bindings.
+fn write_header<W>(dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        mod __gl_imports {{
+            pub use std::cell::{{Cell, RefCell}};
+            pub use std::mem;
+            pub use std::os::raw;
+            pub use std::ptr;
+            pub use std::sync::Arc;
+        }}
+    "#
+    )
+}
+
+/// Creates a `types` module which contains all the type aliases.
+///
+/// See also `generators::gen_types`.
+fn write_type_aliases<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        r#"
+        pub mod types {{
+            #![allow(non_camel_case_types, non_snake_case, dead_code, missing_copy_implementations)]
+    "#
+    )?;
+
+    super::gen_types(registry, dest)?;
+
+    writeln!(
+        dest,
+        "
+        }}
+    "
+    )
+}
+
+/// Creates all the `<enum>` elements at the root of the bindings.
+fn write_enums<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for enm in &registry.enums {
+        super::gen_deprecated_attr(enm.removed_in, options, dest)?;
+        super::gen_enum_item(enm, "types::", dest)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the functions corresponding to the GL commands.
+///
+/// Each function calls the pointer at its index in the current table of the `current` module
+///  created by `write_current`.
+fn write_fns<W>(registry: &Registry, options: &GeneratorOptions, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    for (index, cmd) in registry.cmds.iter().enumerate() {
+        if let Some(v) = registry.aliases.get(&cmd.proto.ident) {
+            writeln!(dest, "/// Fallbacks: {}", v.join(", "))?;
+        }
+        super::gen_provenance_doc(cmd.introduced_in, &cmd.extensions, dest)?;
+        super::gen_deprecated_attr(cmd.removed_in, options, dest)?;
+
+        let check = super::gen_missing_fn_check(cmd, options, &format!("current::get({})", index));
+        writeln!(dest,
+            "#[allow(non_snake_case, unused_variables, dead_code)] #[inline] {missing_fn_attr}
+            pub unsafe fn {name}({params}) -> {return_suffix} {{ \
+                {check} \
+                __gl_imports::mem::transmute::<_, extern \"{abi}\" fn({typed_params}) -> {return_suffix}>\
+                    (fn_ptr)({idents}) \
+            }}",
+            name = cmd.proto.ident,
+            missing_fn_attr = super::gen_missing_fn_attr(options),
+            check = check,
+            params = super::gen_parameters(cmd, true, true).join(", "),
+            abi = super::gen_call_abi(options),
+            typed_params = super::gen_parameters(cmd, false, true).join(", "),
+            return_suffix = cmd.proto.ty,
+            idents = super::gen_parameters(cmd, true, false).join(", "),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Creates the `SYMBOLS` string table.
+///
+/// The table has one line per command, in the order of the pointer table. Each line holds the
+///  symbol of the command, followed by its fallbacks, separated by spaces.
+fn write_symbols<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest, "const SYMBOLS: &str = \"\\")?;
+
+    for c in &registry.cmds {
+        let mut symbols = vec![super::gen_symbol_name(registry.api, &c.proto.ident)];
+        if let Some(v) = registry.aliases.get(&c.proto.ident) {
+            symbols.extend(
+                v.iter()
+                    .map(|name| super::gen_symbol_name(registry.api, name)),
+            );
+        }
+        writeln!(dest, "{}\\n\\", symbols.join(" "))?;
+    }
+
+    writeln!(dest, "\";")
+}
+
+/// Creates the table type, which holds the function pointers loaded for one context.
+fn write_table<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(dest,
+                  "
+        /// The function pointers loaded for one context.
+        ///
+        /// Cloning the table is cheap, and the clones share the same pointers.
+        #[derive(Clone)]
+        pub struct {table} {{
+            /// The function pointers, in the order of `SYMBOLS`. The pointers of the functions
+            /// that could not be loaded point to their stub in `missing_fns`.
+            fns: __gl_imports::Arc<[*const __gl_imports::raw::c_void]>,
+        }}
+
+        // The table only holds function pointers, which can be called from any thread.
+        unsafe impl Send for {table} {{}}
+        unsafe impl Sync for {table} {{}}
+
+        #[allow(dead_code)]
+        impl {table} {{
+            /// Load each OpenGL symbol using a custom load function. This allows for the
+            /// use of functions like `glfwGetProcAddress` or `SDL_GL_GetProcAddress`.
+            ///
+            /// The context the symbols are loaded for should be current when calling this.
+            /// ~~~ignore
+            /// let table = gl::{table}::load_with(|s| window.get_proc_address(s));
+            /// ~~~
+            pub fn load_with<F>(mut loadfn: F) -> {table} where F: FnMut(&'static str) -> *const __gl_imports::raw::c_void {{
+                #[inline(never)]
+                fn inner(loadfn: &mut dyn FnMut(&'static str) -> *const __gl_imports::raw::c_void) -> {table} {{
+                    let fns = SYMBOLS
+                        .lines()
+                        .enumerate()
+                        .map(|(index, symbols)| {{
+                            let mut ptr = __gl_imports::ptr::null();
+                            for symbol in symbols.split(' ') {{
+                                ptr = loadfn(symbol);
+                                if !ptr.is_null() {{ break; }}
+                            }}
+                            if ptr.is_null() {{
+                                missing_fn_ptr(index)
+                            }} else {{
+                                ptr
+                            }}
+                        }})
+                        .collect::<Vec<_>>();
+                    {table} {{ fns: fns.into() }}
+                }}
+
+                inner(&mut loadfn)
+            }}
+
+            /// Returns `true` if the function with the given symbol, such as `\"glClear\"`,
+            /// has been successfully loaded in this table.
+            pub fn is_loaded(&self, symbol: &str) -> bool {{
+                SYMBOLS
+                    .lines()
+                    .position(|symbols| symbols.split(' ').next() == Some(symbol))
+                    .map_or(false, |index| {{
+                        self.fns[index] != missing_fn_ptr(index)
+                    }})
+            }}
+        }}
+    ",
+                  table = table_name(registry))
+}
+
+/// Creates the `current` module, which holds the table that is current on each thread, and the
+///  `make_current` and `clear_current` functions.
+fn write_current<W>(registry: &Registry, dest: &mut W) -> io::Result<()>
+where
+    W: io::Write,
+{
+    writeln!(
+        dest,
+        "
+        mod current {{
+            use super::__gl_imports::{{ptr, raw, Cell, RefCell}};
+            use super::{table};
+
+            thread_local! {{
+                /// The table that is current on this thread, kept alive while it is current.
+                pub static TABLE: RefCell<Option<{table}>> = RefCell::new(None);
+                /// The function pointers of `TABLE`, or null if no table is current.
+                pub static FNS: Cell<*const *const raw::c_void> = Cell::new(ptr::null());
+            }}
+
+            /// Returns the function pointer of the current table that will be used when calling
+            /// the function, or its stub in `missing_fns` if no table is current.
+            #[inline]
+            pub fn get(index: usize) -> *const raw::c_void {{
+                FNS.with(|fns| {{
+                    let fns = fns.get();
+                    if fns.is_null() {{
+                        super::missing_fn_ptr(index)
+                    }} else {{
+                        unsafe {{ *fns.add(index) }}
+                    }}
+                }})
+            }}
+        }}
+
+        /// Makes `table` the current table of the calling thread. The functions called on this
+        /// thread will use it until another table is made current.
+        /// ~~~ignore
+        /// window.make_current();
+        /// gl::make_current(&table);
+        /// ~~~
+        #[allow(dead_code)]
+        pub fn make_current(table: &{table}) {{
+            current::TABLE.with(|current| {{
+                current::FNS.with(|fns| fns.set(table.fns.as_ptr()));
+                *current.borrow_mut() = Some(table.clone());
+            }})
+        }}
+
+        /// Leaves the calling thread without a current table. Calling a function after this is
+        /// handled like calling a function that could not be loaded.
+        #[allow(dead_code)]
+        pub fn clear_current() {{
+            current::FNS.with(|fns| fns.set(__gl_imports::ptr::null()));
+            current::TABLE.with(|current| current.borrow_mut().take());
+        }}
+
+        /// Returns `true` if the function with the given symbol, such as `\"glClear\"`, has been
+        /// successfully loaded in the current table of the calling thread.
+        #[allow(dead_code)]
+        pub fn is_loaded(symbol: &str) -> bool {{
+            current::TABLE.with(|current| {{
+                current
+                    .borrow()
+                    .as_ref()
+                    .map_or(false, |table| table.is_loaded(symbol))
+            }})
+        }}
+    ",
+        table = table_name(registry)
+    )
+}
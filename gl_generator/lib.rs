@@ -71,11 +71,18 @@ mod generators;
 
 mod registry;
 
+pub use generators::checked_struct_gen::CheckedStructGenerator;
 pub use generators::debug_struct_gen::DebugStructGenerator;
 pub use generators::global_gen::GlobalGenerator;
+pub use generators::hook_struct_gen::HookStructGenerator;
+pub use generators::lazy_gen::LazyGenerator;
 pub use generators::static_gen::StaticGenerator;
 pub use generators::static_struct_gen::StaticStructGenerator;
+pub use generators::stats_struct_gen::StatsStructGenerator;
 pub use generators::struct_gen::StructGenerator;
-pub use generators::Generator;
+pub use generators::table_gen::TableGenerator;
+pub use generators::thread_local_gen::ThreadLocalGenerator;
+pub use generators::trait_gen::{DefaultBodies, TraitGenerator};
+pub use generators::{Generator, GeneratorOptions, MissingFn};
 
 pub use registry::*;
@@ -15,13 +15,18 @@
 extern crate khronos_api;
 
 use std::borrow::Cow;
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
+use std::error;
 use std::fmt;
+use std::fs::File;
 use std::hash::{Hash, Hasher};
 use std::io;
+use std::mem;
 use std::ops::{Add, AddAssign};
+use std::path::Path;
 
-use Generator;
+use {Generator, GeneratorOptions};
 
 mod parse;
 
@@ -66,13 +71,37 @@ pub enum Profile {
     Compatibility,
 }
 
+/// The version of an API to build a registry for.
+///
+/// A `(major, minor)` tuple converts into `Version::Exact`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Version {
+    Exact(u8, u8),
+    /// The newest version of the API in the registry.
+    Latest,
+}
+
+impl From<(u8, u8)> for Version {
+    fn from((major, minor): (u8, u8)) -> Version {
+        Version::Exact(major, minor)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub struct Enum {
     pub ident: String,
     pub value: String,
     pub cast: bool,
     pub alias: Option<String>,
     pub ty: Cow<'static, str>,
+    /// The earliest selected version of the API that requires this enum, if any.
+    pub introduced_in: Option<(u8, u8)>,
+    /// The selected extensions that require this enum.
+    pub extensions: Vec<String>,
+    /// The version of the API whose core profile removed this enum, if it is only here because
+    /// the compatibility profile was requested.
+    pub removed_in: Option<(u8, u8)>,
 }
 
 impl Hash for Enum {
@@ -82,10 +111,28 @@ impl Hash for Enum {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub struct Binding {
     pub ident: String,
     pub ty: Cow<'static, str>,
     pub group: Option<String>,
+    pub len: Option<Len>,
+}
+
+/// How many elements a pointer parameter points to, from its `len` attribute.
+///
+/// Parameter names are given as they appear in `Binding::ident`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Len {
+    /// A fixed number of elements, like `len="4"`.
+    Fixed(u32),
+    /// As many elements as another parameter says, like `len="count"`.
+    Param(String),
+    /// An expression in terms of other parameters, like `len="count*4"`.
+    Expr(String),
+    /// A size that depends on the listed parameters in a way the registry doesn't spell out,
+    /// like `len="COMPSIZE(format,type,width)"`.
+    CompSize(Vec<String>),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -96,12 +143,20 @@ pub struct Group {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
 pub struct Cmd {
     pub proto: Binding,
     pub params: Vec<Binding>,
     pub alias: Option<String>,
     pub vecequiv: Option<String>,
     pub glx: Option<GlxOpcode>,
+    /// The earliest selected version of the API that requires this command, if any.
+    pub introduced_in: Option<(u8, u8)>,
+    /// The selected extensions that require this command.
+    pub extensions: Vec<String>,
+    /// The version of the API whose core profile removed this command, if it is only here because
+    /// the compatibility profile was requested.
+    pub removed_in: Option<(u8, u8)>,
 }
 
 impl Hash for Cmd {
@@ -116,62 +171,171 @@ pub struct GlxOpcode {
     pub name: Option<String>,
 }
 
+/// A type declared in the `<types>` section of the registry.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Type {
+    pub ident: String,
+    pub def: TypeDef,
+    pub requires: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// The definition of a `Type`, already translated to Rust.
+///
+/// Type names are relative to the generated `types` module, so `GLint` rather than
+/// `types::GLint`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TypeDef {
+    /// A type the registry only names, such as one from a platform header.
+    External,
+    /// C source that has no Rust counterpart, such as an `#include`.
+    Source(String),
+    /// `typedef T name;`
+    Alias(String),
+    /// A struct that is only ever used behind a pointer, or a type whose definition can't be
+    /// translated, such as a union.
+    Opaque,
+    /// A struct with the given fields.
+    Struct(Vec<Binding>),
+    /// A nullable function pointer.
+    FnPtr {
+        abi: &'static str,
+        params: Vec<Binding>,
+        ret: String,
+    },
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct Registry {
     pub api: Api,
+    /// The version of the API that was requested, or the newest one if that was
+    /// `Version::Latest`.
+    pub version: (u8, u8),
     pub enums: BTreeSet<Enum>,
     pub cmds: BTreeSet<Cmd>,
     pub aliases: BTreeMap<String, Vec<String>>,
     pub groups: BTreeMap<String, Group>,
+    pub types: BTreeMap<String, Type>,
+    /// The extensions that were included, after resolving the filter's selectors.
+    pub extensions: BTreeSet<String>,
 }
 
 impl Registry {
-    pub fn new<'a, Exts>(
+    /// Builds a registry from the bundled Khronos XML files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the registry can not be built. See `Registry::try_new` for a version that
+    /// returns the error instead.
+    pub fn new<'a, V, Exts>(
         api: Api,
-        version: (u8, u8),
+        version: V,
         profile: Profile,
         fallbacks: Fallbacks,
         extensions: Exts,
     ) -> Registry
     where
+        V: Into<Version>,
         Exts: AsRef<[&'a str]>,
     {
-        let (major, minor) = version;
-        let extensions = extensions.as_ref().iter().map(<&str>::to_string).collect();
+        Registry::try_new(api, version, profile, fallbacks, extensions)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let filter = parse::Filter {
-            api,
-            fallbacks,
-            extensions,
-            version: format!("{}.{}", major, minor),
-            profile,
-        };
+    /// Builds a registry from the bundled Khronos XML files, returning an error if the
+    /// requested version or extensions are not available, or if the XML can not be parsed.
+    pub fn try_new<'a, V, Exts>(
+        api: Api,
+        version: V,
+        profile: Profile,
+        fallbacks: Fallbacks,
+        extensions: Exts,
+    ) -> Result<Registry, RegistryError>
+    where
+        V: Into<Version>,
+        Exts: AsRef<[&'a str]>,
+    {
+        Registry::from_filter(&Filter::new(api, version, profile, fallbacks, extensions))
+    }
 
-        let src_raw = match api {
+    /// Builds a registry from the bundled Khronos XML files, like `Registry::try_new`, but
+    /// with extensions chosen by the `filter`'s selectors as well as by name.
+    pub fn from_filter(filter: &Filter) -> Result<Registry, RegistryError> {
+        let src = match filter.api {
             Api::Gl | Api::GlCore | Api::Gles1 | Api::Gles2 | Api::Glsc2 => khronos_api::GL_XML,
             Api::Glx => khronos_api::GLX_XML,
             Api::Wgl => khronos_api::WGL_XML,
             Api::Egl => khronos_api::EGL_XML,
         };
+        let is_gl = !matches!(filter.api, Api::Glx | Api::Wgl | Api::Egl);
 
-        let src = if &src_raw[..BYTE_ORDER_MARK.len()] == BYTE_ORDER_MARK {
-            &src_raw[BYTE_ORDER_MARK.len()..]
-        } else {
-            src_raw
+        let mut known = BTreeSet::new();
+        let mut registry = parse::from_xml_collecting_extensions(
+            strip_byte_order_mark(src),
+            filter,
+            true,
+            &mut known,
+        )?;
+        // the extension files don't have features, so pin the version that was found above
+        let angle_filter = Filter {
+            version: registry.version.into(),
+            ..filter.clone()
         };
-
-        let mut registry = parse::from_xml(src, &filter, true);
-        if filter.extensions.iter().any(|e| e.starts_with("GL_ANGLE_")) {
-            registry += parse::from_xml(khronos_api::GL_ANGLE_EXT_XML, &filter, false);
+        if filter.extensions.iter().any(|e| e.starts_with("GL_ANGLE_"))
+            || (is_gl && !filter.selectors.is_empty())
+        {
+            let angle = khronos_api::GL_ANGLE_EXT_XML;
+            registry +=
+                parse::from_xml_collecting_extensions(angle, &angle_filter, false, &mut known)?;
         }
         if filter
             .extensions
             .iter()
             .any(|e| e.starts_with("EGL_ANGLE_"))
+            || (filter.api == Api::Egl && !filter.selectors.is_empty())
         {
-            registry += parse::from_xml(khronos_api::EGL_ANGLE_EXT_XML, &filter, false);
+            let angle = khronos_api::EGL_ANGLE_EXT_XML;
+            registry +=
+                parse::from_xml_collecting_extensions(angle, &angle_filter, false, &mut known)?;
         }
-        registry
+        add_gl_types(&mut registry)?;
+        check_extension_names(filter, &known)?;
+        Ok(registry)
+    }
+
+    /// Builds a registry from a `gl.xml`, `glx.xml`, `wgl.xml` or `egl.xml` file read from
+    /// `src`, instead of the copies bundled with `khronos_api`.
+    ///
+    /// This is useful when a newer or patched registry is needed. The file must contain a
+    /// `<feature>` for the version requested by the filter.
+    pub fn from_reader<R>(mut src: R, filter: &Filter) -> Result<Registry, RegistryError>
+    where
+        R: io::Read,
+    {
+        let mut buf = Vec::new();
+        src.read_to_end(&mut buf)?;
+        let mut known = BTreeSet::new();
+        let mut registry = parse::from_xml_collecting_extensions(
+            strip_byte_order_mark(&buf),
+            filter,
+            true,
+            &mut known,
+        )?;
+        add_gl_types(&mut registry)?;
+        check_extension_names(filter, &known)?;
+        Ok(registry)
+    }
+
+    /// Builds a registry from the registry XML file at `path`.
+    ///
+    /// See `Registry::from_reader`.
+    pub fn from_path<P>(path: P, filter: &Filter) -> Result<Registry, RegistryError>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        Registry::from_reader(file, filter)
     }
 
     pub fn write_bindings<W, G>(&self, generator: G, output: &mut W) -> io::Result<()>
@@ -182,6 +346,20 @@ impl Registry {
         generator.write(&self, output)
     }
 
+    /// Like `write_bindings`, but passes `options` on to the generator.
+    pub fn write_bindings_with_options<W, G>(
+        &self,
+        generator: G,
+        options: &GeneratorOptions,
+        output: &mut W,
+    ) -> io::Result<()>
+    where
+        G: Generator,
+        W: io::Write,
+    {
+        generator.write_with_options(self, options, output)
+    }
+
     /// Returns a set of all the types used in the supplied registry. This is useful
     /// for working out what conversions are needed for the specific registry.
     pub fn get_tys(&self) -> BTreeSet<&str> {
@@ -196,6 +374,90 @@ impl Registry {
     }
 }
 
+/// Reports the extensions named in `filter` that aren't in the registry, as a warning or, in
+/// strict mode, as an error.
+fn check_extension_names(filter: &Filter, known: &BTreeSet<String>) -> Result<(), RegistryError> {
+    for name in filter.extensions.difference(known) {
+        let err = RegistryError::UnknownExtension {
+            extension: name.clone(),
+            suggestions: suggest_extensions(name, known),
+        };
+        if filter.strict {
+            return Err(err);
+        }
+        warn!("{}", err);
+    }
+    Ok(())
+}
+
+/// Returns the names in `known` that are closest to `name`, for catching typos.
+fn suggest_extensions(name: &str, known: &BTreeSet<String>) -> Vec<String> {
+    const MAX_DISTANCE: usize = 3;
+    const MAX_SUGGESTIONS: usize = 3;
+
+    let mut candidates: Vec<_> = known
+        .iter()
+        .map(|known| (edit_distance(name, known), known))
+        .filter(|&(distance, _)| distance <= MAX_DISTANCE)
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, known)| known.clone())
+        .collect()
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..b.len() + 1).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Defines the GL types that glx.xml and wgl.xml only name, like `GLenum`, using the
+/// `<types>` of the bundled gl.xml. The other GL types are added as well, so that
+/// `glx::types` and `wgl::types` can be used like `gl::types`.
+fn add_gl_types(registry: &mut Registry) -> Result<(), RegistryError> {
+    if !matches!(registry.api, Api::Glx | Api::Wgl) {
+        return Ok(());
+    }
+    let gl_types = parse::types_from_xml(strip_byte_order_mark(khronos_api::GL_XML), Api::Gl)?;
+    for (ident, ty) in gl_types {
+        match registry.types.entry(ident) {
+            Entry::Occupied(mut ent) if ent.get().def == TypeDef::External => {
+                ent.insert(ty);
+            },
+            Entry::Occupied(_) => {},
+            Entry::Vacant(ent) => {
+                ent.insert(ty);
+            },
+        }
+    }
+    Ok(())
+}
+
+fn strip_byte_order_mark(src: &[u8]) -> &[u8] {
+    if src.starts_with(BYTE_ORDER_MARK) {
+        &src[BYTE_ORDER_MARK.len()..]
+    } else {
+        src
+    }
+}
+
 impl Add for Registry {
     type Output = Registry;
 
@@ -207,8 +469,516 @@ impl Add for Registry {
 
 impl AddAssign for Registry {
     fn add_assign(&mut self, other: Self) {
-        self.enums.extend(other.enums);
-        self.cmds.extend(other.cmds);
+        let enums = mem::take(&mut self.enums);
+        self.enums = merge_by_ident(
+            enums,
+            other.enums,
+            |enm| &enm.ident,
+            |a, b| {
+                a.introduced_in = earliest(a.introduced_in, b.introduced_in);
+                a.removed_in = earliest(a.removed_in, b.removed_in);
+                merge_extensions(&mut a.extensions, b.extensions);
+            },
+        );
+        let cmds = mem::take(&mut self.cmds);
+        self.cmds = merge_by_ident(
+            cmds,
+            other.cmds,
+            |cmd| &cmd.proto.ident,
+            |a, b| {
+                a.introduced_in = earliest(a.introduced_in, b.introduced_in);
+                a.removed_in = earliest(a.removed_in, b.removed_in);
+                merge_extensions(&mut a.extensions, b.extensions);
+            },
+        );
         self.aliases.extend(other.aliases);
+        self.types.extend(other.types);
+        self.extensions.extend(other.extensions);
+    }
+}
+
+/// Combines two sets of enums or commands, merging the provenance of items that appear in both.
+fn merge_by_ident<T, I, M>(a: BTreeSet<T>, b: BTreeSet<T>, ident: I, merge: M) -> BTreeSet<T>
+where
+    T: Ord,
+    I: Fn(&T) -> &str,
+    M: Fn(&mut T, T),
+{
+    let mut items: BTreeMap<String, T> = a
+        .into_iter()
+        .map(|item| (ident(&item).to_string(), item))
+        .collect();
+    for item in b {
+        match items.entry(ident(&item).to_string()) {
+            Entry::Occupied(mut ent) => merge(ent.get_mut(), item),
+            Entry::Vacant(ent) => {
+                ent.insert(item);
+            },
+        }
+    }
+    items.into_values().collect()
+}
+
+fn earliest(a: Option<(u8, u8)>, b: Option<(u8, u8)>) -> Option<(u8, u8)> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn merge_extensions(extensions: &mut Vec<String>, other: Vec<String>) {
+    for extension in other {
+        if !extensions.contains(&extension) {
+            extensions.push(extension);
+        }
+    }
+}
+
+/// Selects which parts of a registry XML file end up in a `Registry`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub api: Api,
+    pub version: Version,
+    pub profile: Profile,
+    pub fallbacks: Fallbacks,
+    /// The exact names of the extensions to include.
+    pub extensions: BTreeSet<String>,
+    /// Further extensions to include. Unlike `extensions`, these only ever match extensions
+    /// that support `api`.
+    pub selectors: Vec<ExtensionSelector>,
+    /// Fail with `RegistryError::UnknownExtension` if one of `extensions` is not in the
+    /// registry, instead of logging a warning.
+    pub strict: bool,
+}
+
+impl Filter {
+    /// Creates a filter, taking the same arguments as `Registry::new`.
+    pub fn new<'a, V, Exts>(
+        api: Api,
+        version: V,
+        profile: Profile,
+        fallbacks: Fallbacks,
+        extensions: Exts,
+    ) -> Filter
+    where
+        V: Into<Version>,
+        Exts: AsRef<[&'a str]>,
+    {
+        Filter {
+            api,
+            version: version.into(),
+            profile,
+            fallbacks,
+            extensions: extensions.as_ref().iter().map(<&str>::to_string).collect(),
+            selectors: Vec::new(),
+            strict: false,
+        }
+    }
+
+    /// Also includes the extensions matched by `selector`.
+    pub fn select(mut self, selector: ExtensionSelector) -> Filter {
+        self.selectors.push(selector);
+        self
+    }
+
+    /// Turns strict mode on or off. See `Filter::strict`.
+    pub fn strict(mut self, strict: bool) -> Filter {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns `true` if the extension named `name`, which supports `apis`, is selected.
+    ///
+    /// Extensions named in `extensions` are selected even if they don't support `api`.
+    pub fn selects(&self, name: &str, apis: &[Api]) -> bool {
+        self.extensions.contains(name)
+            || (apis.contains(&self.api) && self.selectors.iter().any(|s| s.matches(name)))
+    }
+}
+
+/// Picks out extensions by something other than their exact name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtensionSelector {
+    /// Extensions whose names match a pattern, where `*` stands for any run of characters and
+    /// `?` for a single character, such as `GL_ARB_*`.
+    Pattern(String),
+    /// Extensions from the given vendors, such as `ARB` or `KHR`.
+    Vendors(Vec<String>),
+    /// Every extension.
+    All,
+}
+
+impl ExtensionSelector {
+    /// Returns `true` if the extension named `name` is selected.
+    pub fn matches(&self, name: &str) -> bool {
+        match *self {
+            ExtensionSelector::Pattern(ref pattern) => {
+                glob_matches(pattern.as_bytes(), name.as_bytes())
+            },
+            ExtensionSelector::Vendors(ref vendors) => match name.split('_').nth(1) {
+                Some(vendor) => vendors.iter().any(|v| v == vendor),
+                None => false,
+            },
+            ExtensionSelector::All => true,
+        }
+    }
+}
+
+fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&b'*', rest)) => (0..name.len() + 1).any(|i| glob_matches(rest, &name[i..])),
+        Some((&b'?', rest)) => !name.is_empty() && glob_matches(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_matches(rest, &name[1..]),
+    }
+}
+
+/// A location in a registry XML file, counted from 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub line: u64,
+    pub column: u64,
+}
+
+impl fmt::Display for SourcePosition {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The reasons a `Registry` can fail to be built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The registry file could not be read.
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    /// The XML is not well-formed.
+    Xml {
+        message: String,
+        position: SourcePosition,
+    },
+    /// An element or some text was found where the registry schema doesn't allow it.
+    UnexpectedElement {
+        expected: String,
+        found: String,
+        position: SourcePosition,
+    },
+    /// An element is missing an attribute that is required by the registry schema.
+    MissingAttribute {
+        element: String,
+        attribute: String,
+        position: SourcePosition,
+    },
+    /// An `api` or `supported` attribute names an API that is not known to the generator.
+    UnknownApi {
+        name: String,
+        position: SourcePosition,
+    },
+    /// A `profile` attribute names a profile that is not known to the generator.
+    UnknownProfile {
+        name: String,
+        position: SourcePosition,
+    },
+    /// An `<enum>` has a `type` attribute that the generator doesn't know how to translate.
+    UnhandledEnumType {
+        ident: String,
+        ty: String,
+        position: Option<SourcePosition>,
+    },
+    /// An `<enum>` has a value that the generator doesn't know how to translate.
+    MalformedEnumValue {
+        ident: String,
+        value: String,
+        position: Option<SourcePosition>,
+    },
+    /// A command uses a C type that the generator doesn't know how to translate.
+    UnknownType {
+        ty: String,
+        position: SourcePosition,
+    },
+    /// The registry doesn't contain a `<feature>` for the requested version.
+    UnknownVersion { api: Api, version: (u8, u8) },
+    /// `Version::Latest` was requested, but the registry doesn't contain any `<feature>` for
+    /// the requested API.
+    NoVersions { api: Api },
+    /// A `<feature>` has a `number` that isn't of the form `major.minor`.
+    MalformedVersion {
+        number: String,
+        position: SourcePosition,
+    },
+    /// An extension was requested that isn't supported by the requested API.
+    UnsupportedExtension { extension: String, api: Api },
+    /// An extension was requested that isn't in the registry. `suggestions` lists similarly
+    /// named extensions that are.
+    UnknownExtension {
+        extension: String,
+        suggestions: Vec<String>,
+    },
+}
+
+impl RegistryError {
+    /// Fills in the position of errors that were created without one.
+    fn at(self, at: SourcePosition) -> RegistryError {
+        match self {
+            RegistryError::UnhandledEnumType {
+                ident,
+                ty,
+                position: None,
+            } => RegistryError::UnhandledEnumType {
+                ident,
+                ty,
+                position: Some(at),
+            },
+            RegistryError::MalformedEnumValue {
+                ident,
+                value,
+                position: None,
+            } => RegistryError::MalformedEnumValue {
+                ident,
+                value,
+                position: Some(at),
+            },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegistryError::Io { ref message, .. } => {
+                write!(fmt, "could not read the registry: {}", message)
+            },
+            RegistryError::Xml {
+                ref message,
+                position,
+            } => write!(fmt, "{}: malformed XML: {}", position, message),
+            RegistryError::UnexpectedElement {
+                ref expected,
+                ref found,
+                position,
+            } => write!(fmt, "{}: expected {}, found {}", position, expected, found),
+            RegistryError::MissingAttribute {
+                ref element,
+                ref attribute,
+                position,
+            } => write!(
+                fmt,
+                "{}: <{}> is missing the `{}` attribute",
+                position, element, attribute
+            ),
+            RegistryError::UnknownApi { ref name, position } => {
+                write!(fmt, "{}: unsupported API `{}`", position, name)
+            },
+            RegistryError::UnknownProfile { ref name, position } => {
+                write!(fmt, "{}: unsupported profile `{}`", position, name)
+            },
+            RegistryError::UnhandledEnumType {
+                ref ident,
+                ref ty,
+                position,
+            } => {
+                if let Some(position) = position {
+                    write!(fmt, "{}: ", position)?;
+                }
+                write!(fmt, "unhandled enum type `{}` for {}", ty, ident)
+            },
+            RegistryError::MalformedEnumValue {
+                ref ident,
+                ref value,
+                position,
+            } => {
+                if let Some(position) = position {
+                    write!(fmt, "{}: ", position)?;
+                }
+                write!(fmt, "unexpected value format `{}` for {}", value, ident)
+            },
+            RegistryError::UnknownType { ref ty, position } => write!(
+                fmt,
+                "{}: type conversion not implemented for `{}`",
+                position, ty
+            ),
+            RegistryError::UnknownVersion {
+                api,
+                version: (major, minor),
+            } => write!(
+                fmt,
+                "did not find version {}.{} of the {} API in the registry",
+                major, minor, api
+            ),
+            RegistryError::NoVersions { api } => {
+                write!(
+                    fmt,
+                    "did not find any version of the {} API in the registry",
+                    api
+                )
+            },
+            RegistryError::MalformedVersion {
+                ref number,
+                position,
+            } => write!(fmt, "{}: malformed version number `{}`", position, number),
+            RegistryError::UnsupportedExtension { ref extension, api } => write!(
+                fmt,
+                "requested {}, which doesn't support the {} API",
+                extension, api
+            ),
+            RegistryError::UnknownExtension {
+                ref extension,
+                ref suggestions,
+            } => {
+                write!(
+                    fmt,
+                    "did not find the {} extension in the registry",
+                    extension
+                )?;
+                if !suggestions.is_empty() {
+                    let suggestions: Vec<_> =
+                        suggestions.iter().map(|s| format!("`{}`", s)).collect();
+                    write!(fmt, "; did you mean {}?", suggestions.join(" or "))?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl error::Error for RegistryError {}
+
+impl From<io::Error> for RegistryError {
+    fn from(err: io::Error) -> RegistryError {
+        RegistryError::Io {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use registry::{ExtensionSelector, Filter, Registry, RegistryError, TypeDef};
+    use std::io;
+    use {Api, Fallbacks, Profile};
+
+    const REGISTRY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<registry>
+    <commands namespace="EGL">
+        <command>
+            <proto><ptype>EGLint</ptype> <name>eglGetError</name></proto>
+        </command>
+    </commands>
+    <feature api="egl" name="EGL_VERSION_1_0" number="1.0">
+        <require>
+            <command name="eglGetError"/>
+        </require>
+    </feature>
+    <extensions>
+        <extension name="EGL_KHR_debug" supported="egl"/>
+        <extension name="EGL_KHR_fence_sync" supported="egl"/>
+    </extensions>
+</registry>
+"#;
+
+    #[test]
+    fn test_from_reader_with_byte_order_mark() {
+        let filter = Filter::new(Api::Egl, (1, 0), Profile::Core, Fallbacks::All, []);
+        let mut src = b"\xef\xbb\xbf".to_vec();
+        src.extend_from_slice(REGISTRY.as_bytes());
+
+        let registry = Registry::from_reader(&src[..], &filter).unwrap();
+        assert_eq!(registry.api, Api::Egl);
+        assert_eq!(registry.cmds.len(), 1);
+    }
+
+    #[test]
+    fn test_glx_defines_gl_types() {
+        let registry = Registry::new(Api::Glx, (1, 4), Profile::Core, Fallbacks::All, []);
+        assert_eq!(
+            registry.types["GLenum"].def,
+            TypeDef::Alias("super::__gl_imports::raw::c_uint".to_string())
+        );
+        assert!(registry.types.contains_key("GLDEBUGPROC"));
+        assert_eq!(registry.types["Display"].def, TypeDef::External);
+    }
+
+    #[test]
+    fn test_add_merges_provenance() {
+        let filter = Filter::new(Api::Egl, (1, 0), Profile::Core, Fallbacks::All, []);
+        let registry = Registry::from_reader(REGISTRY.as_bytes(), &filter).unwrap();
+        let mut other = registry.clone();
+        other.cmds = other
+            .cmds
+            .into_iter()
+            .map(|mut cmd| {
+                cmd.introduced_in = None;
+                cmd.extensions = vec!["EGL_KHR_debug".to_string()];
+                cmd
+            })
+            .collect();
+
+        let registry = registry + other;
+        assert_eq!(registry.cmds.len(), 1);
+        let cmd = registry.cmds.iter().next().unwrap();
+        assert_eq!(cmd.introduced_in, Some((1, 0)));
+        assert_eq!(cmd.extensions, vec!["EGL_KHR_debug".to_string()]);
+    }
+
+    #[test]
+    fn test_extension_selector_matches() {
+        let pattern = ExtensionSelector::Pattern("GL_ARB_*_buffer?".to_string());
+        assert!(pattern.matches("GL_ARB_vertex_buffers"));
+        assert!(!pattern.matches("GL_ARB_vertex_buffer"));
+        assert!(!pattern.matches("GL_EXT_vertex_buffers"));
+
+        let vendors = ExtensionSelector::Vendors(vec!["ARB".to_string(), "KHR".to_string()]);
+        assert!(vendors.matches("GL_KHR_debug"));
+        assert!(vendors.matches("EGL_KHR_image"));
+        assert!(!vendors.matches("GL_EXT_debug_label"));
+        assert!(!vendors.matches("GL_ARBdebug"));
+    }
+
+    #[test]
+    fn test_unknown_extension() {
+        let filter = Filter::new(
+            Api::Egl,
+            (1, 0),
+            Profile::Core,
+            Fallbacks::All,
+            ["EGL_KHR_debgu"],
+        );
+        assert!(Registry::from_reader(REGISTRY.as_bytes(), &filter).is_ok());
+
+        let filter = filter.strict(true);
+        let err = Registry::from_reader(REGISTRY.as_bytes(), &filter).unwrap_err();
+        assert_eq!(
+            err,
+            RegistryError::UnknownExtension {
+                extension: "EGL_KHR_debgu".to_string(),
+                suggestions: vec!["EGL_KHR_debug".to_string()],
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "did not find the EGL_KHR_debgu extension in the registry; \
+             did you mean `EGL_KHR_debug`?"
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(super::edit_distance("", "abc"), 3);
+        assert_eq!(super::edit_distance("debug", "debug"), 0);
+        assert_eq!(super::edit_distance("ouptut", "output"), 2);
+        assert_eq!(super::edit_distance("sitting", "kitten"), 3);
+    }
+
+    #[test]
+    fn test_from_path_missing_file() {
+        let filter = Filter::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, []);
+        match Registry::from_path("does/not/exist/gl.xml", &filter) {
+            Err(RegistryError::Io { kind, .. }) => assert_eq!(kind, io::ErrorKind::NotFound),
+            result => panic!("unexpected result: {:?}", result),
+        }
     }
 }
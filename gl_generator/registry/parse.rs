@@ -19,18 +19,42 @@ use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::io;
 use xml::attribute::OwnedAttribute;
+use xml::common::Position;
 use xml::reader::XmlEvent;
 use xml::EventReader as XmlEventReader;
 
-use registry::{Binding, Cmd, Enum, GlxOpcode, Group, Registry};
+use registry::{
+    Binding, Cmd, Enum, Filter, GlxOpcode, Group, Len, Registry, RegistryError, SourcePosition,
+    Type, TypeDef, Version,
+};
 use {Api, Fallbacks, Profile};
 
-pub fn from_xml<R: io::Read>(src: R, filter: &Filter, require_feature: bool) -> Registry {
-    XmlEventReader::new(src)
-        .into_iter()
-        .map(Result::unwrap)
-        .filter_map(ParseEvent::from_xml)
-        .parse(filter, require_feature)
+#[cfg(test)]
+pub fn from_xml<R: io::Read>(
+    src: R,
+    filter: &Filter,
+    require_feature: bool,
+) -> Result<Registry, RegistryError> {
+    from_xml_collecting_extensions(src, filter, require_feature, &mut BTreeSet::new())
+}
+
+/// Builds a registry from `src`, adding the name of every extension in the file to
+/// `known_extensions` whether or not it was selected.
+pub fn from_xml_collecting_extensions<R: io::Read>(
+    src: R,
+    filter: &Filter,
+    require_feature: bool,
+    known_extensions: &mut BTreeSet<String>,
+) -> Result<Registry, RegistryError> {
+    Events::new(src).parse(filter, require_feature, known_extensions)
+}
+
+/// Reads the `<types>` section of `src` as seen by `api`, ignoring the rest of the file.
+pub fn types_from_xml<R: io::Read>(
+    src: R,
+    api: Api,
+) -> Result<BTreeMap<String, Type>, RegistryError> {
+    Events::new(src).parse_types(api)
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -84,6 +108,63 @@ impl ParseEvent {
             XmlEvent::Whitespace(_) => None,
         }
     }
+
+    fn describe(&self) -> String {
+        match *self {
+            ParseEvent::Start(ref name, _) => format!("<{}>", name),
+            ParseEvent::End(ref name) => format!("</{}>", name),
+            ParseEvent::Text(ref text) => format!("text {:?}", text),
+        }
+    }
+}
+
+/// The stream of `ParseEvent`s read from a registry XML file, remembering where the
+/// last event came from so that errors can point at it.
+struct Events<R: io::Read> {
+    reader: XmlEventReader<R>,
+    position: SourcePosition,
+    finished: bool,
+}
+
+impl<R: io::Read> Events<R> {
+    fn new(src: R) -> Events<R> {
+        Events {
+            reader: XmlEventReader::new(src),
+            position: SourcePosition { line: 1, column: 1 },
+            finished: false,
+        }
+    }
+}
+
+impl<R: io::Read> Iterator for Events<R> {
+    type Item = Result<ParseEvent, RegistryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let event = self.reader.next();
+            let position = self.reader.position();
+            self.position = SourcePosition {
+                line: position.row + 1,
+                column: position.column + 1,
+            };
+            match event {
+                Ok(XmlEvent::EndDocument) => self.finished = true,
+                Ok(event) => {
+                    if let Some(event) = ParseEvent::from_xml(event) {
+                        return Some(Ok(event));
+                    }
+                },
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(RegistryError::Xml {
+                        message: err.msg().to_string(),
+                        position: self.position,
+                    }));
+                },
+            }
+        }
+        None
+    }
 }
 
 fn api_from_str(src: &str) -> Result<Option<Api>, ()> {
@@ -118,6 +199,7 @@ fn underscore_numeric_prefix(src: &str) -> String {
 
 fn underscore_keyword(ident: String) -> String {
     match ident.as_ref() {
+        "box" => "box_".to_string(),
         "in" => "in_".to_string(),
         "ref" => "ref_".to_string(),
         "type" => "type_".to_string(),
@@ -125,6 +207,25 @@ fn underscore_keyword(ident: String) -> String {
     }
 }
 
+fn make_len(src: &str) -> Len {
+    let src = src.trim();
+    if let Ok(count) = src.parse() {
+        Len::Fixed(count)
+    } else if src.starts_with("COMPSIZE(") && src.ends_with(')') {
+        let params = src["COMPSIZE(".len()..src.len() - 1]
+            .split(',')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(|param| underscore_keyword(param.to_string()))
+            .collect();
+        Len::CompSize(params)
+    } else if is_identifier(src) {
+        Len::Param(underscore_keyword(src.to_string()))
+    } else {
+        Len::Expr(src.to_string())
+    }
+}
+
 fn trim_str<'a>(s: &'a str, trim: &str) -> &'a str {
     if s.starts_with(trim) {
         &s[trim.len()..]
@@ -143,7 +244,28 @@ fn trim_enum_prefix(ident: &str, api: Api) -> String {
     underscore_numeric_prefix(ident)
 }
 
-fn make_enum(ident: String, ty: Option<String>, value: String, alias: Option<String>) -> Enum {
+fn malformed_enum_value(ident: String, value: String) -> RegistryError {
+    RegistryError::MalformedEnumValue {
+        ident,
+        value,
+        position: None,
+    }
+}
+
+fn unhandled_enum_type(ident: String, ty: String) -> RegistryError {
+    RegistryError::UnhandledEnumType {
+        ident,
+        ty,
+        position: None,
+    }
+}
+
+fn make_enum(
+    ident: String,
+    ty: Option<String>,
+    value: String,
+    alias: Option<String>,
+) -> Result<Enum, RegistryError> {
     let (ty, value, cast) = {
         if value.starts_with("((") && value.ends_with(")") {
             // Some enums have a value of the form `'((' type ')' expr ')'`.
@@ -157,14 +279,15 @@ fn make_enum(ident: String, ty: Option<String>, value: String, alias: Option<Str
 
                 (Cow::Owned(ty), value, true)
             } else {
-                panic!("Unexpected value format: {}", value)
+                return Err(malformed_enum_value(ident, value));
             }
         } else {
             let ty = match ty {
                 Some(ref ty) if ty == "u" => "GLuint",
                 Some(ref ty) if ty == "ull" => "GLuint64",
-                Some(ty) => panic!("Unhandled enum type: {}", ty),
+                Some(ty) => return Err(unhandled_enum_type(ident, ty)),
                 None if value.starts_with("\"") => "&'static str",
+                None if value.starts_with('-') => "GLint",
                 None if ident == "TRUE" || ident == "FALSE" => "GLboolean",
                 None => "GLenum",
             };
@@ -172,16 +295,24 @@ fn make_enum(ident: String, ty: Option<String>, value: String, alias: Option<Str
         }
     };
 
-    Enum {
+    Ok(Enum {
         ident: ident,
         value: value,
         cast: cast,
         alias: alias,
         ty: ty,
-    }
+        introduced_in: None,
+        extensions: Vec::new(),
+        removed_in: None,
+    })
 }
 
-fn make_egl_enum(ident: String, ty: Option<String>, value: String, alias: Option<String>) -> Enum {
+fn make_egl_enum(
+    ident: String,
+    ty: Option<String>,
+    value: String,
+    alias: Option<String>,
+) -> Result<Enum, RegistryError> {
     let (ty, value, cast) = {
         if value.starts_with("EGL_CAST(") && value.ends_with(")") {
             // Handling "SpecialNumbers" in the egl.xml file
@@ -193,17 +324,17 @@ fn make_egl_enum(ident: String, ty: Option<String>, value: String, alias: Option
 
                 (Cow::Owned(ty), value, true)
             } else {
-                panic!("Unexpected value format: {}", value)
+                return Err(malformed_enum_value(ident, value));
             }
         } else {
             match value.chars().next() {
                 Some('-') | Some('0'..='9') => (),
-                _ => panic!("Unexpected value format: {}", value),
+                _ => return Err(malformed_enum_value(ident, value)),
             }
 
             let ty = match ty {
                 Some(ref ty) if ty == "ull" => "EGLuint64KHR",
-                Some(ty) => panic!("Unhandled enum type: {}", ty),
+                Some(ty) => return Err(unhandled_enum_type(ident, ty)),
                 None if value.starts_with('-') => "EGLint",
                 None if ident == "TRUE" || ident == "FALSE" => "EGLBoolean",
                 None => "EGLenum",
@@ -212,13 +343,16 @@ fn make_egl_enum(ident: String, ty: Option<String>, value: String, alias: Option
         }
     };
 
-    Enum {
+    Ok(Enum {
         ident: ident,
         value: value,
         cast: cast,
         alias: alias,
         ty: ty,
-    }
+        introduced_in: None,
+        extensions: Vec::new(),
+        removed_in: None,
+    })
 }
 
 fn trim_cmd_prefix(ident: &str, api: Api) -> &str {
@@ -230,7 +364,10 @@ fn trim_cmd_prefix(ident: &str, api: Api) -> &str {
     }
 }
 
-fn merge_map(a: &mut BTreeMap<String, Vec<String>>, b: BTreeMap<String, Vec<String>>) {
+/// The commands that alias each command, keyed by the name of the aliased command.
+type Aliases = BTreeMap<String, Vec<String>>;
+
+fn merge_map(a: &mut Aliases, b: Aliases) {
     for (k, v) in b {
         match a.entry(k) {
             Entry::Occupied(mut ent) => {
@@ -247,13 +384,17 @@ fn merge_map(a: &mut BTreeMap<String, Vec<String>>, b: BTreeMap<String, Vec<Stri
 struct Feature {
     pub api: Api,
     pub name: String,
-    pub number: String,
+    pub number: (u8, u8),
     pub requires: Vec<Require>,
     pub removes: Vec<Remove>,
 }
 
 #[derive(Clone)]
 struct Require {
+    /// Only applies to this api, if set
+    pub api: Option<Api>,
+    /// Only applies to this profile, if set
+    pub profile: Option<Profile>,
     /// A reference to the earlier types, by name
     pub enums: Vec<String>,
     /// A reference to the earlier types, by name
@@ -262,6 +403,8 @@ struct Require {
 
 #[derive(Clone)]
 struct Remove {
+    /// Only applies to this api, if set
+    pub api: Option<Api>,
     // always Core, for now
     pub profile: Profile,
     /// A reference to the earlier types, by name
@@ -270,6 +413,21 @@ struct Remove {
     pub commands: Vec<String>,
 }
 
+impl Require {
+    fn applies_to(&self, filter: &Filter) -> bool {
+        self.api.map_or(true, |api| api == filter.api)
+            && self
+                .profile
+                .map_or(true, |profile| profile == filter.profile)
+    }
+}
+
+impl Remove {
+    fn applies_to(&self, filter: &Filter) -> bool {
+        self.api.map_or(true, |api| api == filter.api)
+    }
+}
+
 #[derive(Clone)]
 struct Extension {
     pub name: String,
@@ -278,17 +436,60 @@ struct Extension {
     pub requires: Vec<Require>,
 }
 
-pub struct Filter {
-    pub api: Api,
-    pub fallbacks: Fallbacks,
-    pub extensions: BTreeSet<String>,
-    pub profile: Profile,
-    pub version: String,
+/// Which of the selected features and extensions require an enum or command, and which
+/// removed it from the core profile.
+#[derive(Default)]
+struct Provenance {
+    introduced_in: Option<(u8, u8)>,
+    extensions: Vec<String>,
+    removed_in: Option<(u8, u8)>,
 }
 
-trait Parse: Sized + Iterator<Item = ParseEvent> {
-    fn parse(mut self, filter: &Filter, require_feature: bool) -> Registry {
-        self.consume_start_element("registry");
+impl Provenance {
+    fn entry<'a>(desired: &'a mut BTreeMap<String, Provenance>, name: &str) -> &'a mut Provenance {
+        desired.entry(name.to_string()).or_default()
+    }
+
+    fn add_version(&mut self, version: (u8, u8)) {
+        // features are visited in order, so the first version seen is the earliest
+        if self.introduced_in.is_none() {
+            self.introduced_in = Some(version);
+        }
+    }
+
+    fn add_removal(&mut self, version: (u8, u8)) {
+        if self.removed_in.is_none() {
+            self.removed_in = Some(version);
+        }
+    }
+
+    fn add_extension(&mut self, extension: &str) {
+        if !self.extensions.iter().any(|e| e == extension) {
+            self.extensions.push(extension.to_string());
+        }
+    }
+}
+
+/// Parses a feature number such as `"4.5"`.
+fn parse_version(number: &str) -> Option<(u8, u8)> {
+    let mut parts = number.splitn(2, '.');
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => Some((major.parse().ok()?, minor.parse().ok()?)),
+        _ => None,
+    }
+}
+
+trait Parse: Sized + Iterator<Item = Result<ParseEvent, RegistryError>> {
+    /// The position of the most recently consumed event.
+    fn location(&self) -> SourcePosition;
+
+    fn parse(
+        mut self,
+        filter: &Filter,
+        require_feature: bool,
+        known_extensions: &mut BTreeSet<String>,
+    ) -> Result<Registry, RegistryError> {
+        self.consume_start_element("registry")?;
 
         let mut enums = Vec::new();
         let mut cmds = Vec::new();
@@ -296,22 +497,28 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         let mut extensions = Vec::new();
         let mut aliases = BTreeMap::new();
         let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+        let mut types = BTreeMap::new();
 
         while let Some(event) = self.next() {
-            match event {
+            match event? {
                 // ignores
                 ParseEvent::Text(_) => (),
-                ParseEvent::Start(ref name, _) if name == "comment" => self.skip_to_end("comment"),
-                ParseEvent::Start(ref name, _) if name == "types" => self.skip_to_end("types"),
+                ParseEvent::Start(ref name, _) if name == "comment" => {
+                    self.skip_to_end("comment")?
+                },
+                // add type definitions
+                ParseEvent::Start(ref name, _) if name == "types" => {
+                    types.extend(self.consume_types(filter.api)?);
+                },
 
                 // add group namespace
                 ParseEvent::Start(ref name, _) if name == "groups" => {
-                    groups.extend(self.consume_groups(filter.api));
+                    groups.extend(self.consume_groups(filter.api)?);
                 },
 
                 // add enum namespace
                 ParseEvent::Start(ref name, ref attributes) if name == "enums" => {
-                    enums.extend(self.consume_enums(filter.api));
+                    enums.extend(self.consume_enums(filter.api)?);
                     let enums_group = get_attribute(&attributes, "group");
                     let enums_type = get_attribute(&attributes, "type");
                     if let Some(group) = enums_group.and_then(|name| groups.get_mut(&name)) {
@@ -321,23 +528,23 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
 
                 // add command namespace
                 ParseEvent::Start(ref name, _) if name == "commands" => {
-                    let (new_cmds, new_aliases) = self.consume_cmds(filter.api);
+                    let (new_cmds, new_aliases) = self.consume_cmds(filter.api)?;
                     cmds.extend(new_cmds);
                     merge_map(&mut aliases, new_aliases);
                 },
 
                 ParseEvent::Start(ref name, ref attributes) if name == "feature" => {
                     debug!("Parsing feature: {:?}", attributes);
-                    features.push(Feature::convert(&mut self, &attributes));
+                    features.push(Feature::convert(&mut self, name, attributes)?);
                 },
 
                 ParseEvent::Start(ref name, _) if name == "extensions" => loop {
-                    match self.next().unwrap() {
+                    match self.next_event()? {
                         ParseEvent::Start(ref name, ref attributes) if name == "extension" => {
-                            extensions.push(Extension::convert(&mut self, &attributes));
+                            extensions.push(Extension::convert(&mut self, name, attributes)?);
                         },
                         ParseEvent::End(ref name) if name == "extensions" => break,
-                        event => panic!("Unexpected message {:?}", event),
+                        event => return Err(self.unexpected("<extension>", &event)),
                     }
                 },
 
@@ -345,24 +552,59 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                 ParseEvent::End(ref name) if name == "registry" => break,
 
                 // error handling
-                event => panic!("Expected </registry>, found: {:?}", event),
+                event => return Err(self.unexpected("</registry>", &event)),
             }
         }
 
-        let mut desired_enums = BTreeSet::new();
-        let mut desired_cmds = BTreeSet::new();
+        let mut desired_enums = BTreeMap::new();
+        let mut desired_cmds = BTreeMap::new();
 
         // find the features we want
+        let latest = features
+            .iter()
+            .filter(|feature| feature.api == filter.api)
+            .map(|feature| feature.number)
+            .max();
+        let version = match filter.version {
+            Version::Exact(major, minor) => (major, minor),
+            Version::Latest => match latest {
+                Some(version) => version,
+                None if require_feature => {
+                    return Err(RegistryError::NoVersions { api: filter.api })
+                },
+                // nothing will be selected from the features anyway
+                None => (0, 0),
+            },
+        };
         let mut found_feature = false;
         for feature in &features {
-            // XXX: verify that the string comparison with <= actually works as desired
-            if feature.api == filter.api && feature.number <= filter.version {
+            if feature.api == filter.api && feature.number <= version {
+                let number = feature.number;
                 for require in &feature.requires {
-                    desired_enums.extend(require.enums.iter().map(|x| x.clone()));
-                    desired_cmds.extend(require.commands.iter().map(|x| x.clone()));
+                    if require.applies_to(filter) {
+                        for enm in &require.enums {
+                            Provenance::entry(&mut desired_enums, enm).add_version(number);
+                        }
+                        for cmd in &require.commands {
+                            Provenance::entry(&mut desired_cmds, cmd).add_version(number);
+                        }
+                    }
+                    if require.profile == Some(Profile::Core) {
+                        // items that come back to the core profile are no longer removed
+                        for enm in &require.enums {
+                            if let Some(provenance) = desired_enums.get_mut(enm) {
+                                provenance.removed_in = None;
+                            }
+                        }
+                        for cmd in &require.commands {
+                            if let Some(provenance) = desired_cmds.get_mut(cmd) {
+                                provenance.removed_in = None;
+                            }
+                        }
+                    }
                 }
 
-                for remove in &feature.removes {
+                for remove in feature.removes.iter().filter(|r| r.applies_to(filter)) {
                     if remove.profile == filter.profile {
                         for enm in &remove.enums {
                             debug!("Removing {}", enm);
@@ -372,91 +614,179 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                             debug!("Removing {}", cmd);
                             desired_cmds.remove(cmd);
                         }
+                    } else if remove.profile == Profile::Core {
+                        for enm in &remove.enums {
+                            if let Some(provenance) = desired_enums.get_mut(enm) {
+                                provenance.add_removal(number);
+                            }
+                        }
+                        for cmd in &remove.commands {
+                            if let Some(provenance) = desired_cmds.get_mut(cmd) {
+                                provenance.add_removal(number);
+                            }
+                        }
                     }
                 }
             }
-            if feature.number == filter.version {
+            if feature.number == version {
                 found_feature = true;
             }
         }
 
         if !found_feature && require_feature {
-            panic!("Did not find version {} in the registry", filter.version);
+            return Err(RegistryError::UnknownVersion {
+                api: filter.api,
+                version,
+            });
         }
 
+        let mut selected_extensions = BTreeSet::new();
         for extension in &extensions {
-            if filter.extensions.contains(&extension.name) {
+            known_extensions.insert(extension.name.clone());
+            if filter.selects(&extension.name, &extension.supported) {
                 if !extension.supported.contains(&filter.api) {
-                    panic!(
-                        "Requested {}, which doesn't support the {} API",
-                        extension.name, filter.api
-                    );
+                    return Err(RegistryError::UnsupportedExtension {
+                        extension: extension.name.clone(),
+                        api: filter.api,
+                    });
                 }
-                for require in &extension.requires {
-                    desired_enums.extend(require.enums.iter().map(|x| x.clone()));
-                    desired_cmds.extend(require.commands.iter().map(|x| x.clone()));
+                selected_extensions.insert(extension.name.clone());
+                for require in extension.requires.iter().filter(|r| r.applies_to(filter)) {
+                    for enm in &require.enums {
+                        Provenance::entry(&mut desired_enums, enm).add_extension(&extension.name);
+                    }
+                    for cmd in &require.commands {
+                        Provenance::entry(&mut desired_cmds, cmd).add_extension(&extension.name);
+                    }
                 }
             }
         }
 
-        let is_desired_enum = |e: &Enum| {
-            desired_enums.contains(&("GL_".to_string() + &e.ident))
-                || desired_enums.contains(&("WGL_".to_string() + &e.ident))
-                || desired_enums.contains(&("GLX_".to_string() + &e.ident))
-                || desired_enums.contains(&("EGL_".to_string() + &e.ident))
+        let desired_enum = |mut e: Enum| {
+            let provenance = ["GL_", "WGL_", "GLX_", "EGL_"]
+                .iter()
+                .filter_map(|prefix| desired_enums.get(&(prefix.to_string() + &e.ident)))
+                .next()?;
+            e.introduced_in = provenance.introduced_in;
+            e.extensions = provenance.extensions.clone();
+            e.removed_in = provenance.removed_in;
+            Some(e)
         };
 
-        let is_desired_cmd = |c: &Cmd| {
-            desired_cmds.contains(&("gl".to_string() + &c.proto.ident))
-                || desired_cmds.contains(&("wgl".to_string() + &c.proto.ident))
-                || desired_cmds.contains(&("glX".to_string() + &c.proto.ident))
-                || desired_cmds.contains(&("egl".to_string() + &c.proto.ident))
+        let desired_cmd = |mut c: Cmd| {
+            let provenance = ["gl", "wgl", "glX", "egl"]
+                .iter()
+                .filter_map(|prefix| desired_cmds.get(&(prefix.to_string() + &c.proto.ident)))
+                .next()?;
+            c.introduced_in = provenance.introduced_in;
+            c.extensions = provenance.extensions.clone();
+            c.removed_in = provenance.removed_in;
+            Some(c)
         };
 
-        Registry {
+        Ok(Registry {
             api: filter.api,
-            enums: enums.into_iter().filter(is_desired_enum).collect(),
-            cmds: cmds.into_iter().filter(is_desired_cmd).collect(),
+            version,
+            enums: enums.into_iter().filter_map(desired_enum).collect(),
+            cmds: cmds.into_iter().filter_map(desired_cmd).collect(),
             aliases: if filter.fallbacks == Fallbacks::None {
                 BTreeMap::new()
             } else {
                 aliases
             },
             groups,
+            types,
+            extensions: selected_extensions,
+        })
+    }
+
+    fn parse_types(mut self, api: Api) -> Result<BTreeMap<String, Type>, RegistryError> {
+        self.consume_start_element("registry")?;
+        loop {
+            match self.next_event()? {
+                ParseEvent::Start(ref name, _) if name == "types" => {
+                    return self.consume_types(api)
+                },
+                ParseEvent::Start(ref name, _) => self.skip_to_end(name)?,
+                ParseEvent::End(ref name) if name == "registry" => return Ok(BTreeMap::new()),
+                _ => {},
+            }
+        }
+    }
+
+    fn next_event(&mut self) -> Result<ParseEvent, RegistryError> {
+        match self.next() {
+            Some(event) => event,
+            None => Err(RegistryError::Xml {
+                message: "unexpected end of document".to_string(),
+                position: self.location(),
+            }),
+        }
+    }
+
+    fn unexpected(&self, expected: &str, found: &ParseEvent) -> RegistryError {
+        RegistryError::UnexpectedElement {
+            expected: expected.to_string(),
+            found: found.describe(),
+            position: self.location(),
         }
     }
 
-    fn consume_characters(&mut self) -> String {
-        match self.next().unwrap() {
-            ParseEvent::Text(ch) => ch,
-            event => panic!("Expected characters, found: {:?}", event),
+    fn require_attribute(
+        &self,
+        attributes: &[Attribute],
+        element: &str,
+        key: &str,
+    ) -> Result<String, RegistryError> {
+        get_attribute(attributes, key).ok_or_else(|| RegistryError::MissingAttribute {
+            element: element.to_string(),
+            attribute: key.to_string(),
+            position: self.location(),
+        })
+    }
+
+    fn api_from_attribute(&self, src: &str) -> Result<Option<Api>, RegistryError> {
+        api_from_str(src).map_err(|()| RegistryError::UnknownApi {
+            name: src.to_string(),
+            position: self.location(),
+        })
+    }
+
+    fn consume_characters(&mut self) -> Result<String, RegistryError> {
+        match self.next_event()? {
+            ParseEvent::Text(ch) => Ok(ch),
+            event => Err(self.unexpected("characters", &event)),
         }
     }
 
-    fn consume_start_element(&mut self, expected_name: &str) -> Vec<Attribute> {
-        match self.next().unwrap() {
+    fn consume_start_element(
+        &mut self,
+        expected_name: &str,
+    ) -> Result<Vec<Attribute>, RegistryError> {
+        match self.next_event()? {
             ParseEvent::Start(name, attributes) => {
                 if expected_name == name {
-                    attributes
+                    Ok(attributes)
                 } else {
-                    panic!("Expected <{}>, found: <{}>", expected_name, name)
+                    let found = ParseEvent::Start(name, attributes);
+                    Err(self.unexpected(&format!("<{}>", expected_name), &found))
                 }
             },
-            event => panic!("Expected <{}>, found: {:?}", expected_name, event),
+            event => Err(self.unexpected(&format!("<{}>", expected_name), &event)),
         }
     }
 
-    fn consume_end_element(&mut self, expected_name: &str) {
-        match self.next().unwrap() {
-            ParseEvent::End(ref name) if expected_name == name => (),
-            event => panic!("Expected </{}>, found: {:?}", expected_name, event),
+    fn consume_end_element(&mut self, expected_name: &str) -> Result<(), RegistryError> {
+        match self.next_event()? {
+            ParseEvent::End(ref name) if expected_name == name => Ok(()),
+            event => Err(self.unexpected(&format!("</{}>", expected_name), &event)),
         }
     }
 
-    fn skip_to_end(&mut self, expected_name: &str) {
+    fn skip_to_end(&mut self, expected_name: &str) -> Result<(), RegistryError> {
         loop {
-            match self.next().unwrap() {
-                ParseEvent::End(ref name) if expected_name == name => break,
+            match self.next_event()? {
+                ParseEvent::End(ref name) if expected_name == name => return Ok(()),
                 _ => {},
             }
         }
@@ -467,14 +797,14 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         one: &'a str,
         two: &'a str,
         end: &'a str,
-    ) -> (Vec<T>, Vec<U>) {
+    ) -> Result<(Vec<T>, Vec<U>), RegistryError> {
         debug!("consume_two: looking for {} and {} until {}", one, two, end);
 
         let mut ones = Vec::new();
         let mut twos = Vec::new();
 
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Start(ref name, ref attributes) => {
                     debug!("Found start element <{:?} {:?}>", name, attributes);
                     debug!("one and two are {} and {}", one, two);
@@ -482,7 +812,7 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                     let n = name.clone();
 
                     if one == n {
-                        ones.push(FromXml::convert(self, &attributes));
+                        ones.push(FromXml::convert(self, one, attributes)?);
                     } else if "type" == n {
                         // XXX: GL1.1 contains types, which we never care about anyway.
                         // Make sure consume_two doesn't get used for things which *do*
@@ -490,9 +820,10 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                         warn!("Ignoring type!");
                         continue;
                     } else if two == n {
-                        twos.push(FromXml::convert(self, &attributes));
+                        twos.push(FromXml::convert(self, two, attributes)?);
                     } else {
-                        panic!("Unexpected element: <{:?} {:?}>", n, &attributes);
+                        let expected = format!("<{}> or <{}>", one, two);
+                        return Err(self.unexpected(&expected, &ParseEvent::Start(n, Vec::new())));
                     }
                 },
                 ParseEvent::End(ref name) => {
@@ -507,95 +838,215 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                         warn!("Ignoring type!");
                         continue;
                     } else if end == name {
-                        return (ones, twos);
+                        return Ok((ones, twos));
                     } else {
-                        panic!("Unexpected end element {:?}", name);
+                        let expected = format!("</{}>", end);
+                        return Err(self.unexpected(&expected, &ParseEvent::End(name.clone())));
+                    }
+                },
+                event => return Err(self.unexpected(&format!("</{}>", end), &event)),
+            }
+        }
+    }
+
+    fn consume_types(&mut self, api: Api) -> Result<BTreeMap<String, Type>, RegistryError> {
+        let mut types = BTreeMap::new();
+        loop {
+            match self.next_event()? {
+                // ignores
+                ParseEvent::Text(_) => {},
+                ParseEvent::Start(ref name, _) if name == "comment" => {
+                    self.skip_to_end("comment")?
+                },
+
+                // add type definition, preferring the one meant for this api
+                ParseEvent::Start(ref name, ref attributes) if name == "type" => {
+                    let (ty, tag) = self.consume_type(attributes)?;
+                    let specific = match get_attribute(attributes, "api") {
+                        Some(ref type_api) => match self.api_from_attribute(type_api)? {
+                            Some(type_api) if type_api == api => true,
+                            _ => continue,
+                        },
+                        None => false,
+                    };
+                    if let Some(tag) = tag {
+                        types.entry(tag.clone()).or_insert(Type {
+                            ident: tag,
+                            def: TypeDef::Opaque,
+                            requires: None,
+                            comment: None,
+                        });
+                    }
+                    if specific || !types.contains_key(&ty.ident) {
+                        types.insert(ty.ident.clone(), ty);
                     }
                 },
-                event => panic!("Unexpected message {:?}", event),
+
+                // finished building the namespace
+                ParseEvent::End(ref name) if name == "types" => break,
+                // error handling
+                event => return Err(self.unexpected("</types>", &event)),
             }
         }
+        Ok(types)
     }
 
-    fn consume_enums(&mut self, api: Api) -> Vec<Enum> {
+    /// Consumes a `<type>` element, also returning the name of any struct that it declares
+    /// in passing, like `__GLsync` in `typedef struct __GLsync *GLsync;`.
+    fn consume_type(
+        &mut self,
+        attributes: &[Attribute],
+    ) -> Result<(Type, Option<String>), RegistryError> {
+        let position = self.location();
+        let mut ident = get_attribute(attributes, "name");
+        let mut named = false;
+        let mut apientry = false;
+        let mut before = String::new();
+        let mut after = String::new();
+        loop {
+            match self.next_event()? {
+                ParseEvent::Text(ref text) if named => after.push_str(text),
+                ParseEvent::Text(ref text) => before.push_str(text),
+                ParseEvent::Start(ref name, _) if name == "name" => {
+                    ident = Some(self.consume_characters()?);
+                    self.consume_end_element("name")?;
+                    named = true;
+                },
+                ParseEvent::Start(ref name, _) if name == "apientry" => {
+                    self.consume_end_element("apientry")?;
+                    apientry = true;
+                },
+                ParseEvent::End(ref name) if name == "type" => break,
+                event => return Err(self.unexpected("</type>", &event)),
+            }
+        }
+
+        let ident = ident.ok_or_else(|| RegistryError::MissingAttribute {
+            element: "type".to_string(),
+            attribute: "name".to_string(),
+            position,
+        })?;
+
+        let (ident, def, tag) = if !named {
+            match before.trim() {
+                // like `<type name="int32_t" requires="inttypes"/>`
+                "" if c_primitive(&ident).is_some() => {
+                    let alias = to_rust_type_def(&ident, true);
+                    (ident, alias.map_or(TypeDef::External, TypeDef::Alias), None)
+                },
+                "" => (ident, TypeDef::External, None),
+                source => (ident, TypeDef::Source(source.to_string()), None),
+            }
+        } else if let Some(name) = ident.strip_prefix("struct ") {
+            (name.to_string(), TypeDef::Opaque, None)
+        } else {
+            let before = strip_comments(&before);
+            let after = strip_comments(&after);
+            match make_type_def(before.trim(), after.trim(), apientry) {
+                Some((def, tag)) => (ident, def, tag),
+                // an opaque type still compiles behind a pointer, and points at this type
+                // if a command uses it by value
+                None => {
+                    warn!(
+                        "Could not translate the definition of type {}, declaring it as opaque",
+                        ident
+                    );
+                    (ident, TypeDef::Opaque, None)
+                },
+            }
+        };
+
+        let ty = Type {
+            ident,
+            def,
+            requires: get_attribute(attributes, "requires"),
+            comment: get_attribute(attributes, "comment"),
+        };
+        Ok((ty, tag))
+    }
+
+    fn consume_enums(&mut self, api: Api) -> Result<Vec<Enum>, RegistryError> {
         let mut enums = Vec::new();
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 // ignores
                 ParseEvent::Text(_) => {},
-                ParseEvent::Start(ref name, _) if name == "unused" => self.skip_to_end("unused"),
+                ParseEvent::Start(ref name, _) if name == "unused" => self.skip_to_end("unused")?,
 
                 // add enum definition
                 ParseEvent::Start(ref name, ref attributes) if name == "enum" => {
-                    enums.push(self.consume_enum(api, attributes));
+                    enums.push(self.consume_enum(api, attributes)?);
                 },
 
                 // finished building the namespace
                 ParseEvent::End(ref name) if name == "enums" => break,
                 // error handling
-                event => panic!("Expected </enums>, found: {:?}", event),
+                event => return Err(self.unexpected("</enums>", &event)),
             }
         }
-        enums
+        Ok(enums)
     }
 
-    fn consume_enum(&mut self, api: Api, attributes: &[Attribute]) -> Enum {
-        let ident = trim_enum_prefix(&get_attribute(&attributes, "name").unwrap(), api).to_string();
-        let value = get_attribute(&attributes, "value").unwrap();
+    fn consume_enum(&mut self, api: Api, attributes: &[Attribute]) -> Result<Enum, RegistryError> {
+        let position = self.location();
+        let ident = self.require_attribute(attributes, "enum", "name")?;
+        let ident = trim_enum_prefix(&ident, api);
+        let value = self.require_attribute(attributes, "enum", "value")?;
         let alias = get_attribute(&attributes, "alias");
         let ty = get_attribute(&attributes, "type");
-        self.consume_end_element("enum");
+        self.consume_end_element("enum")?;
 
-        match api {
+        let enm = match api {
             Api::Egl => make_egl_enum(ident, ty, value, alias),
             _ => make_enum(ident, ty, value, alias),
-        }
+        };
+        enm.map_err(|err| err.at(position))
     }
 
-    fn consume_groups(&mut self, api: Api) -> BTreeMap<String, Group> {
+    fn consume_groups(&mut self, api: Api) -> Result<BTreeMap<String, Group>, RegistryError> {
         let mut groups = BTreeMap::new();
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Start(ref name, ref attributes) if name == "group" => {
-                    let ident = get_attribute(&attributes, "name").unwrap();
+                    let ident = self.require_attribute(attributes, "group", "name")?;
                     let group = Group {
                         ident: ident.clone(),
                         enums_type: None,
-                        enums: self.consume_group_enums(api),
+                        enums: self.consume_group_enums(api)?,
                     };
                     groups.insert(ident, group);
                 },
                 ParseEvent::End(ref name) if name == "groups" => break,
-                event => panic!("Expected </groups>, found: {:?}", event),
+                event => return Err(self.unexpected("</groups>", &event)),
             }
         }
-        groups
+        Ok(groups)
     }
 
-    fn consume_group_enums(&mut self, api: Api) -> Vec<String> {
+    fn consume_group_enums(&mut self, api: Api) -> Result<Vec<String>, RegistryError> {
         let mut enums = Vec::new();
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Start(ref name, ref attributes) if name == "enum" => {
-                    let enum_name = get_attribute(&attributes, "name");
-                    enums.push(trim_enum_prefix(&enum_name.unwrap(), api));
-                    self.consume_end_element("enum");
+                    let enum_name = self.require_attribute(attributes, "enum", "name")?;
+                    enums.push(trim_enum_prefix(&enum_name, api));
+                    self.consume_end_element("enum")?;
                 },
                 ParseEvent::End(ref name) if name == "group" => break,
-                event => panic!("Expected </group>, found: {:?}", event),
+                event => return Err(self.unexpected("</group>", &event)),
             }
         }
-        enums
+        Ok(enums)
     }
 
-    fn consume_cmds(&mut self, api: Api) -> (Vec<Cmd>, BTreeMap<String, Vec<String>>) {
+    fn consume_cmds(&mut self, api: Api) -> Result<(Vec<Cmd>, Aliases), RegistryError> {
         let mut cmds = Vec::new();
-        let mut aliases: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut aliases: Aliases = BTreeMap::new();
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 // add command definition
                 ParseEvent::Start(ref name, _) if name == "command" => {
-                    let new = self.consume_cmd(api);
+                    let new = self.consume_cmd(api)?;
                     if let Some(ref v) = new.alias {
                         match aliases.entry(v.clone()) {
                             Entry::Occupied(mut ent) => {
@@ -611,16 +1062,16 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
                 // finished building the namespace
                 ParseEvent::End(ref name) if name == "commands" => break,
                 // error handling
-                event => panic!("Expected </commands>, found: {:?}", event),
+                event => return Err(self.unexpected("</commands>", &event)),
             }
         }
-        (cmds, aliases)
+        Ok((cmds, aliases))
     }
 
-    fn consume_cmd(&mut self, api: Api) -> Cmd {
+    fn consume_cmd(&mut self, api: Api) -> Result<Cmd, RegistryError> {
         // consume command prototype
-        self.consume_start_element("proto");
-        let mut proto = self.consume_binding("proto", &[]);
+        self.consume_start_element("proto")?;
+        let mut proto = self.consume_binding("proto", &[])?;
         proto.ident = trim_cmd_prefix(&proto.ident, api).to_string();
 
         let mut params = Vec::new();
@@ -628,75 +1079,92 @@ trait Parse: Sized + Iterator<Item = ParseEvent> {
         let mut vecequiv = None;
         let mut glx = None;
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Start(ref name, ref attributes) if name == "param" => {
-                    params.push(self.consume_binding("param", attributes));
+                    params.push(self.consume_binding("param", attributes)?);
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "alias" => {
                     alias = get_attribute(&attributes, "name");
                     alias = alias.map(|t| trim_cmd_prefix(&t, api).to_string());
-                    self.consume_end_element("alias");
+                    self.consume_end_element("alias")?;
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "vecequiv" => {
                     vecequiv = get_attribute(&attributes, "vecequiv");
-                    self.consume_end_element("vecequiv");
+                    self.consume_end_element("vecequiv")?;
                 },
                 ParseEvent::Start(ref name, ref attributes) if name == "glx" => {
                     glx = Some(GlxOpcode {
-                        opcode: get_attribute(&attributes, "opcode").unwrap(),
+                        opcode: self.require_attribute(attributes, "glx", "opcode")?,
                         name: get_attribute(&attributes, "name"),
                     });
-                    self.consume_end_element("glx");
+                    self.consume_end_element("glx")?;
                 },
                 ParseEvent::End(ref name) if name == "command" => break,
-                event => panic!("Expected </command>, found: {:?}", event),
+                event => return Err(self.unexpected("</command>", &event)),
             }
         }
 
-        Cmd {
+        Ok(Cmd {
             proto: proto,
             params: params,
             alias: alias,
             vecequiv: vecequiv,
             glx: glx,
-        }
+            introduced_in: None,
+            extensions: Vec::new(),
+            removed_in: None,
+        })
     }
 
-    fn consume_binding(&mut self, outside_tag: &str, attributes: &[Attribute]) -> Binding {
+    fn consume_binding(
+        &mut self,
+        outside_tag: &str,
+        attributes: &[Attribute],
+    ) -> Result<Binding, RegistryError> {
         // consume type
         let mut ty = String::new();
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Text(text) => ty.push_str(&text),
                 ParseEvent::Start(ref name, _) if name == "ptype" => (),
                 ParseEvent::End(ref name) if name == "ptype" => (),
                 ParseEvent::Start(ref name, _) if name == "name" => break,
-                event => panic!("Expected binding, found: {:?}", event),
+                event => return Err(self.unexpected("<name>", &event)),
             }
         }
 
         // consume identifier
-        let ident = underscore_keyword(self.consume_characters());
-        self.consume_end_element("name");
+        let ident = underscore_keyword(self.consume_characters()?);
+        self.consume_end_element("name")?;
 
         // consume the type suffix
         loop {
-            match self.next().unwrap() {
+            match self.next_event()? {
                 ParseEvent::Text(text) => ty.push_str(&text),
                 ParseEvent::End(ref name) if name == outside_tag => break,
-                event => panic!("Expected binding, found: {:?}", event),
+                event => return Err(self.unexpected(&format!("</{}>", outside_tag), &event)),
             }
         }
 
-        Binding {
+        let rust_ty = to_rust_ty(&ty).ok_or_else(|| RegistryError::UnknownType {
+            ty: ty.trim().to_string(),
+            position: self.location(),
+        })?;
+
+        Ok(Binding {
             ident: ident,
-            ty: to_rust_ty(ty),
+            ty: rust_ty,
             group: get_attribute(&attributes, "group"),
-        }
+            len: get_attribute(attributes, "len").map(|len| make_len(&len)),
+        })
     }
 }
 
-impl<T> Parse for T where T: Sized + Iterator<Item = ParseEvent> {}
+impl<R: io::Read> Parse for Events<R> {
+    fn location(&self) -> SourcePosition {
+        self.position
+    }
+}
 
 fn get_attribute(attribs: &[Attribute], key: &str) -> Option<String> {
     attribs
@@ -705,401 +1173,462 @@ fn get_attribute(attribs: &[Attribute], key: &str) -> Option<String> {
         .map(|attrib| attrib.value.clone())
 }
 
-trait FromXml {
-    fn convert<P: Parse>(parser: &mut P, a: &[Attribute]) -> Self;
+trait FromXml: Sized {
+    fn convert<P: Parse>(
+        parser: &mut P,
+        element: &str,
+        a: &[Attribute],
+    ) -> Result<Self, RegistryError>;
 }
 
 impl FromXml for Require {
-    fn convert<P: Parse>(parser: &mut P, _: &[Attribute]) -> Require {
+    fn convert<P: Parse>(
+        parser: &mut P,
+        _: &str,
+        a: &[Attribute],
+    ) -> Result<Require, RegistryError> {
         debug!("Doing a FromXml on Require");
-        let (enums, commands) = parser.consume_two("enum", "command", "require");
-        Require {
-            enums: enums,
-            commands: commands,
-        }
+        let api = match get_attribute(a, "api") {
+            Some(api) => parser.api_from_attribute(&api)?,
+            None => None,
+        };
+        let profile =
+            match get_attribute(a, "profile") {
+                // the only profile of GLES 1, so it doesn't restrict anything
+                Some(ref profile) if profile == "common" => None,
+                Some(profile) => Some(profile_from_str(&profile).map_err(|()| {
+                    RegistryError::UnknownProfile {
+                        name: profile.clone(),
+                        position: parser.location(),
+                    }
+                })?),
+                None => None,
+            };
+        let (enums, commands) = parser.consume_two("enum", "command", "require")?;
+        Ok(Require {
+            api,
+            profile,
+            enums,
+            commands,
+        })
     }
 }
 
 impl FromXml for Remove {
-    fn convert<P: Parse>(parser: &mut P, a: &[Attribute]) -> Remove {
+    fn convert<P: Parse>(
+        parser: &mut P,
+        element: &str,
+        a: &[Attribute],
+    ) -> Result<Remove, RegistryError> {
         debug!("Doing a FromXml on Remove");
-        let profile = get_attribute(a, "profile").unwrap();
-        let profile = profile_from_str(&profile).unwrap();
-        let (enums, commands) = parser.consume_two("enum", "command", "remove");
-
-        Remove {
-            profile: profile,
-            enums: enums,
-            commands: commands,
-        }
+        let api = match get_attribute(a, "api") {
+            Some(api) => parser.api_from_attribute(&api)?,
+            None => None,
+        };
+        let profile = parser.require_attribute(a, element, "profile")?;
+        let profile = profile_from_str(&profile).map_err(|()| RegistryError::UnknownProfile {
+            name: profile.clone(),
+            position: parser.location(),
+        })?;
+        let (enums, commands) = parser.consume_two("enum", "command", "remove")?;
+
+        Ok(Remove {
+            api,
+            profile,
+            enums,
+            commands,
+        })
     }
 }
 
 impl FromXml for Feature {
-    fn convert<P: Parse>(parser: &mut P, a: &[Attribute]) -> Feature {
+    fn convert<P: Parse>(
+        parser: &mut P,
+        element: &str,
+        a: &[Attribute],
+    ) -> Result<Feature, RegistryError> {
         debug!("Doing a FromXml on Feature");
-        let api = get_attribute(a, "api").unwrap();
-        let api = api_from_str(&api).unwrap().unwrap();
-        let name = get_attribute(a, "name").unwrap();
-        let number = get_attribute(a, "number").unwrap();
+        let api = parser.require_attribute(a, element, "api")?;
+        let api = match parser.api_from_attribute(&api)? {
+            Some(api) => api,
+            None => {
+                return Err(RegistryError::UnknownApi {
+                    name: api,
+                    position: parser.location(),
+                })
+            },
+        };
+        let name = parser.require_attribute(a, element, "name")?;
+        let number = parser.require_attribute(a, element, "number")?;
+        let number = match parse_version(&number) {
+            Some(number) => number,
+            None => {
+                return Err(RegistryError::MalformedVersion {
+                    number,
+                    position: parser.location(),
+                })
+            },
+        };
 
-        debug!("Found api = {}, name = {}, number = {}", api, name, number);
+        debug!(
+            "Found api = {}, name = {}, number = {:?}",
+            api, name, number
+        );
 
-        let (require, remove) = parser.consume_two("require", "remove", "feature");
+        let (require, remove) = parser.consume_two("require", "remove", "feature")?;
 
-        Feature {
+        Ok(Feature {
             api: api,
             name: name,
             number: number,
             requires: require,
             removes: remove,
-        }
+        })
     }
 }
 
 impl FromXml for Extension {
-    fn convert<P: Parse>(parser: &mut P, a: &[Attribute]) -> Extension {
+    fn convert<P: Parse>(
+        parser: &mut P,
+        element: &str,
+        a: &[Attribute],
+    ) -> Result<Extension, RegistryError> {
         debug!("Doing a FromXml on Extension");
-        let name = get_attribute(a, "name").unwrap();
-        let supported = get_attribute(a, "supported")
-            .unwrap()
+        let name = parser.require_attribute(a, element, "name")?;
+        let mut supported = Vec::new();
+        for api in parser
+            .require_attribute(a, element, "supported")?
             .split('|')
-            .filter_map(|api| {
-                api_from_str(api).unwrap_or_else(|()| panic!("unsupported API `{}`", api))
-            })
-            .collect::<Vec<_>>();
+        {
+            supported.extend(parser.api_from_attribute(api)?);
+        }
         let mut require = Vec::new();
         loop {
-            match parser.next().unwrap() {
+            match parser.next_event()? {
                 ParseEvent::Start(ref name, ref attributes) if name == "require" => {
-                    require.push(FromXml::convert(parser, &attributes));
+                    require.push(FromXml::convert(parser, name, attributes)?);
                 },
                 ParseEvent::End(ref name) if name == "extension" => break,
-                event => panic!("Unexpected message {:?}", event),
+                event => return Err(parser.unexpected("</extension>", &event)),
             }
         }
 
-        Extension {
+        Ok(Extension {
             name: name,
             supported: supported,
             requires: require,
-        }
+        })
     }
 }
 
 impl FromXml for String {
-    fn convert<P: Parse>(_: &mut P, a: &[Attribute]) -> String {
-        get_attribute(a, "name").unwrap()
+    fn convert<P: Parse>(
+        parser: &mut P,
+        element: &str,
+        a: &[Attribute],
+    ) -> Result<String, RegistryError> {
+        parser.require_attribute(a, element, "name")
     }
 }
 
-/// Converts a C style type definition to the Rust equivalent
-pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Cow<'static, str> {
-    let ty = match ty.as_ref().trim() {
-        // gl.xml types
-        "GLDEBUGPROC" => "types::GLDEBUGPROC",
-        "GLDEBUGPROCAMD" => "types::GLDEBUGPROCAMD",
-        "GLDEBUGPROCARB" => "types::GLDEBUGPROCARB",
-        "GLDEBUGPROCKHR" => "types::GLDEBUGPROCKHR",
-        "GLbitfield" => "types::GLbitfield",
-        "GLboolean" => "types::GLboolean",
-        "GLbyte" => "types::GLbyte",
-        "GLclampd" => "types::GLclampd",
-        "GLclampf" => "types::GLclampf",
-        "GLclampx" => "types::GLclampx",
-        "GLdouble" => "types::GLdouble",
-        "GLeglImageOES" => "types::GLeglImageOES",
-        "GLenum" => "types::GLenum",
-        "GLfixed" => "types::GLfixed",
-        "GLfloat" => "types::GLfloat",
-        "GLhalfNV" => "types::GLhalfNV",
-        "GLhandleARB" => "types::GLhandleARB",
-        "GLint" => "types::GLint",
-        "GLint64" => "types::GLint64",
-        "GLint64EXT" => "types::GLint64EXT",
-        "GLintptr" => "types::GLintptr",
-        "GLintptrARB" => "types::GLintptrARB",
-        "GLshort" => "types::GLshort",
-        "GLsizei" => "types::GLsizei",
-        "GLsizeiptr" => "types::GLsizeiptr",
-        "GLsizeiptrARB" => "types::GLsizeiptrARB",
-        "GLsync" => "types::GLsync",
-        "GLubyte" => "types::GLubyte",
-        "GLuint" => "types::GLuint",
-        "GLuint64" => "types::GLuint64",
-        "GLuint64EXT" => "types::GLuint64EXT",
-        "GLushort" => "types::GLushort",
-        "GLvdpauSurfaceNV" => "types::GLvdpauSurfaceNV",
-        "void" => "()",
-        "GLboolean *" => "*mut types::GLboolean",
-        "GLchar *" => "*mut types::GLchar",
-        "const GLchar*" => "*const types::GLchar",
-        "GLcharARB *" => "*mut types::GLcharARB",
-        "GLdouble *" => "*mut types::GLdouble",
-        "GLenum *" => "*mut types::GLenum",
-        "GLfixed *" => "*mut types::GLfixed",
-        "GLfloat *" => "*mut types::GLfloat",
-        "GLhandleARB *" => "*mut types::GLhandleARB",
-        "GLint *" => "*mut types::GLint",
-        "GLint64 *" => "*mut types::GLint64",
-        "GLint64EXT *" => "*mut types::GLint64EXT",
-        "GLsizei *" => "*mut types::GLsizei",
-        "GLubyte *" => "*mut types::GLubyte",
-        "GLuint *" => "*mut types::GLuint",
-        "GLuint [2]" => "*mut [types::GLuint; 2]",
-        "GLuint64 *" => "*mut types::GLuint64",
-        "GLuint64EXT *" => "*mut types::GLuint64EXT",
-        "GLushort *" => "*mut types::GLushort",
-        "GLvoid *" => "*mut types::GLvoid",
-        "GLvoid **" => "*const *mut types::GLvoid",
-        "void *" => "*mut __gl_imports::raw::c_void",
-        "void **" => "*const *mut __gl_imports::raw::c_void",
-        "const GLboolean *" => "*const types::GLboolean",
-        "const GLbyte *" => "*const types::GLbyte",
-        "const GLchar *" => "*const types::GLchar",
-        "const GLcharARB *" => "*const types::GLcharARB",
-        "const GLclampf *" => "*const types::GLclampf",
-        "const GLdouble *" => "*const types::GLdouble",
-        "const GLenum *" => "*const types::GLenum",
-        "const GLfixed *" => "*const types::GLfixed",
-        "const GLfloat" => "types::GLfloat",
-        "const GLfloat *" => "*const types::GLfloat",
-        "const GLhalfNV *" => "*const types::GLhalfNV",
-        "const GLint *" => "*const types::GLint",
-        "const GLint*" => "*const types::GLint",
-        "const GLint64 *" => "*const types::GLint64",
-        "const GLint64EXT *" => "*const types::GLint64EXT",
-        "const GLintptr *" => "*const types::GLintptr",
-        "const GLshort *" => "*const types::GLshort",
-        "const GLsizei*" | "const GLsizei *" => "*const types::GLsizei",
-        "const GLsizeiptr *" => "*const types::GLsizeiptr",
-        "const GLubyte *" => "*const types::GLubyte",
-        "const GLuint *" => "*const types::GLuint",
-        "const GLuint64 *" => "*const types::GLuint64",
-        "const GLuint64EXT *" => "*const types::GLuint64EXT",
-        "const GLushort *" => "*const types::GLushort",
-        "const GLvdpauSurfaceNV *" => "*const types::GLvdpauSurfaceNV",
-        "const GLvoid *" => "*const types::GLvoid",
-        "const void*" | "const void *" => "*const __gl_imports::raw::c_void",
-        "const void **" => "*const *const __gl_imports::raw::c_void",
-        "const void *const*" => "*const *const __gl_imports::raw::c_void",
-        "const GLboolean **" => "*const *const types::GLboolean",
-        "const GLchar **" => "*const *const types::GLchar",
-        "const GLcharARB **" => "*const *const types::GLcharARB",
-        "const GLvoid **" => "*const *const types::GLvoid",
-        "const GLchar *const*" => "*const *const types::GLchar",
-        "const GLvoid *const*" => "*const *const types::GLvoid",
-        "struct _cl_context *" => "*const types::_cl_context",
-        "struct _cl_event *" => "*const types::_cl_event",
-        "GLuint[2]" => "[Gluint; 2]",
-
-        // glx.xml types
-        "Bool" => "types::Bool",
-        "Colormap" => "types::Colormap",
-        "DMbuffer" => "types::DMbuffer",
-        "Font" => "types::Font",
-        "GLXContext" => "types::GLXContext",
-        "GLXContextID" => "types::GLXContextID",
-        "GLXDrawable" => "types::GLXDrawable",
-        "GLXFBConfig" => "types::GLXFBConfig",
-        "GLXFBConfigSGIX" => "types::GLXFBConfigSGIX",
-        "GLXPbuffer" => "types::GLXPbuffer",
-        "GLXPbufferSGIX" => "types::GLXPbufferSGIX",
-        "GLXPixmap" => "types::GLXPixmap",
-        "GLXVideoCaptureDeviceNV" => "types::GLXVideoCaptureDeviceNV",
-        "GLXVideoDeviceNV" => "types::GLXVideoDeviceNV",
-        "GLXVideoSourceSGIX" => "types::GLXVideoSourceSGIX",
-        "GLXWindow" => "types::GLXWindow",
-        // "GLboolean"                 => "types::GLboolean",
-        // "GLenum"                    => "types::GLenum",
-        // "GLint"                     => "types::GLint",
-        // "GLsizei"                   => "types::GLsizei",
-        // "GLuint"                    => "types::GLuint",
-        "Pixmap" => "types::Pixmap",
-        "Status" => "types::Status",
-        "VLNode" => "types::VLNode",
-        "VLPath" => "types::VLPath",
-        "VLServer" => "types::VLServer",
-        "Window" => "types::Window",
-        "__GLXextFuncPtr" => "types::__GLXextFuncPtr",
-        "const GLXContext" => "const types::GLXContext",
-        "float" => "__gl_imports::raw::c_float",
-        "int" => "__gl_imports::raw::c_int",
-        "int64_t" => "i64",
-        "unsigned int" => "__gl_imports::raw::c_uint",
-        "unsigned long" => "__gl_imports::raw::c_ulong",
-        // "void "                     => "()",
-        "DMparams *" => "*mut types::DMparams",
-        "Display *" => "*mut types::Display",
-        "GLXFBConfig *" => "*mut types::GLXFBConfig",
-        "GLXFBConfigSGIX *" => "*mut types::GLXFBConfigSGIX",
-        "GLXHyperpipeConfigSGIX *" => "*mut types::GLXHyperpipeConfigSGIX",
-        "GLXHyperpipeNetworkSGIX *" => "*mut types::GLXHyperpipeNetworkSGIX",
-        "GLXVideoCaptureDeviceNV *" => "*mut types::GLXVideoCaptureDeviceNV",
-        "GLXVideoDeviceNV *" => "*mut types::GLXVideoDeviceNV",
-        // "GLuint *"                  => "*mut types::GLuint",
-        "XVisualInfo *" => "*mut types::XVisualInfo",
-        // "const GLubyte *"           => "*GLubyte",
-        "const char *" => "*const __gl_imports::raw::c_char",
-        "const int *" => "*const __gl_imports::raw::c_int",
-        // "const void *"              => "*const __gl_imports::raw::c_void",
-        "int *" => "*mut __gl_imports::raw::c_int",
-        "int32_t *" => "*mut i32",
-        "int64_t *" => "*mut i64",
-        "long *" => "*mut __gl_imports::raw::c_long",
-        "unsigned int *" => "*mut __gl_imports::raw::c_uint",
-        "unsigned long *" => "*mut __gl_imports::raw::c_ulong",
-        // "void *"                    => "*mut __gl_imports::raw::c_void",
-
-        // wgl.xml types
-        "BOOL" => "types::BOOL",
-        "DWORD" => "types::DWORD",
-        "FLOAT" => "types::FLOAT",
-        // "GLbitfield"                => "types::GLbitfield",
-        // "GLboolean"                 => "types::GLboolean",
-        // "GLenum"                    => "types::GLenum",
-        // "GLfloat"                   => "types::GLfloat",
-        // "GLint"                     => "types::GLint",
-        // "GLsizei"                   => "types::GLsizei",
-        // "GLuint"                    => "types::GLuint",
-        // "GLushort"                  => "types::GLushort",
-        "HANDLE" => "types::HANDLE",
-        "HDC" => "types::HDC",
-        "HENHMETAFILE" => "types::HENHMETAFILE",
-        "HGLRC" => "types::HGLRC",
-        "HGPUNV" => "types::HGPUNV",
-        "HPBUFFERARB" => "types::HPBUFFERARB",
-        "HPBUFFEREXT" => "types::HPBUFFEREXT",
-        "HPVIDEODEV" => "types::HPVIDEODEV",
-        "HVIDEOINPUTDEVICENV" => "types::HVIDEOINPUTDEVICENV",
-        "HVIDEOOUTPUTDEVICENV" => "types::HVIDEOOUTPUTDEVICENV",
-        "INT" => "types::INT",
-        "INT64" => "types::INT64",
-        "LPCSTR" => "types::LPCSTR",
-        "LPGLYPHMETRICSFLOAT" => "types::LPGLYPHMETRICSFLOAT",
-        "LPVOID" => "types::LPVOID",
-        "PGPU_DEVICE" => "types::PGPU_DEVICE",
-        "PROC" => "types::PROC",
-        "UINT" => "types::UINT",
-        "VOID" => "types::VOID",
-        // "int "                      => "__gl_imports::raw::c_int",
-        // "unsigned int "             => "__gl_imports::raw::c_uint",
-        // "void "                     => "()",
-        "BOOL *" => "*mut types::BOOL",
-        "DWORD *" => "*mut types::DWORD",
-        "FLOAT *" => "*mut types::FLOAT",
-        // "GLuint *"                  => "*mut types::GLuint",
-        "HANDLE *" => "*mut types::HANDLE",
-        "HGPUNV *" => "*mut types::HGPUNV",
-        "HPVIDEODEV *" => "*mut types::HPVIDEODEV",
-        "HVIDEOINPUTDEVICENV *" => "*mut types::HVIDEOINPUTDEVICENV",
-        "HVIDEOOUTPUTDEVICENV *" => "*mut types::HVIDEOOUTPUTDEVICENV",
-        "INT32 *" => "*mut types::INT32",
-        "INT64 *" => "*mut types::INT64",
-        "UINT *" => "*mut types::UINT",
-        "USHORT *" => "*mut types::USHORT",
-        "const COLORREF *" => "*const types::COLORREF",
-        "const DWORD *" => "*const types::DWORD",
-        "const FLOAT *" => "*const types::FLOAT",
-        // "const GLushort *"          => "*const types::GLushort",
-        "const HANDLE *" => "*const types::HANDLE",
-        "const HGPUNV *" => "*const types::HGPUNV",
-        "const LAYERPLANEDESCRIPTOR *" => "*const types::LAYERPLANEDESCRIPTOR",
-        "const LPVOID *" => "*const types::LPVOID",
-        "const PIXELFORMATDESCRIPTOR *" => "*const types::IXELFORMATDESCRIPTOR",
-        "const USHORT *" => "*const types::USHORT",
-        // "const char *"              => "*const __gl_imports::raw::c_char",
-        // "const int *"               => "*const __gl_imports::raw::c_int",
-        "float *" => "*mut __gl_imports::raw::c_float",
-        // "int *"                     => "*mut __gl_imports::raw::c_int",
-        // "unsigned long *"           => "*mut __gl_imports::raw::c_ulong",
-        // "void *"                    => "*mut __gl_imports::raw::c_void",
-
-        // elx.xml types
-        "khronos_utime_nanoseconds_t" => "types::khronos_utime_nanoseconds_t",
-        "khronos_uint64_t" => "types::khronos_uint64_t",
-        "khronos_ssize_t" => "types::khronos_ssize_t",
-        "EGLNativeDisplayType" => "types::EGLNativeDisplayType",
-        "EGLNativePixmapType" => "types::EGLNativePixmapType",
-        "EGLNativeWindowType" => "types::EGLNativeWindowType",
-        "EGLint" => "types::EGLint",
-        "EGLint *" => "*mut types::EGLint",
-        "const EGLint *" => "*const types::EGLint",
-        "NativeDisplayType" => "types::NativeDisplayType",
-        "NativePixmapType" => "types::NativePixmapType",
-        "NativeWindowType" => "types::NativeWindowType",
-        //"Bool"                      => "types::Bool",
-        "EGLBoolean" => "types::EGLBoolean",
-        "EGLenum" => "types::EGLenum",
-        "EGLAttribKHR" => "types::EGLAttribKHR",
-        "EGLAttrib" => "types::EGLAttrib",
-        "EGLAttrib *" => "*mut types::EGLAttrib",
-        "const EGLAttrib *" => "*const types::EGLAttrib",
-        "const EGLattrib *" => "*const types::EGLAttrib", // Due to a typo in khronos_api/api_angle/scripts/egl_angle_ext.xml - see brendanzab/gl-rs#491
-        "EGLConfig" => "types::EGLConfig",
-        "EGLConfig *" => "*mut types::EGLConfig",
-        "EGLContext" => "types::EGLContext",
-        "EGLDeviceEXT" => "types::EGLDeviceEXT",
-        "EGLDisplay" => "types::EGLDisplay",
-        "EGLSurface" => "types::EGLSurface",
-        "EGLClientBuffer" => "types::EGLClientBuffer",
-        "__eglMustCastToProperFunctionPointerType" => {
-            "types::__eglMustCastToProperFunctionPointerType"
+/// The Rust types that plain C, `<stdint.h>` and `<KHR/khrplatform.h>` types translate to.
+fn c_primitive(ty: &str) -> Option<&'static str> {
+    let ty = match ty {
+        "char" => "c_char",
+        "signed char" => "c_schar",
+        "unsigned char" => "c_uchar",
+        "short" | "short int" | "signed short" => "c_short",
+        "unsigned short" | "unsigned short int" => "c_ushort",
+        "int" | "signed" | "signed int" => "c_int",
+        "unsigned" | "unsigned int" => "c_uint",
+        "long" | "long int" | "signed long" => "c_long",
+        "unsigned long" | "unsigned long int" => "c_ulong",
+        "long long" | "long long int" => "c_longlong",
+        "unsigned long long" | "unsigned long long int" => "c_ulonglong",
+        "float" | "khronos_float_t" => "c_float",
+        "double" => "c_double",
+        "int8_t" | "khronos_int8_t" => "i8",
+        "uint8_t" | "khronos_uint8_t" => "u8",
+        "int16_t" | "khronos_int16_t" => "i16",
+        "uint16_t" | "khronos_uint16_t" => "u16",
+        "int32_t" | "khronos_int32_t" => "i32",
+        "uint32_t" | "khronos_uint32_t" => "u32",
+        "int64_t" | "khronos_int64_t" | "khronos_stime_nanoseconds_t" => "i64",
+        "uint64_t" | "khronos_uint64_t" | "khronos_utime_nanoseconds_t" => "u64",
+        "intptr_t" | "ptrdiff_t" | "ssize_t" | "khronos_intptr_t" | "khronos_ssize_t" => "isize",
+        "uintptr_t" | "size_t" | "khronos_uintptr_t" | "khronos_usize_t" => "usize",
+        _ => return None,
+    };
+    Some(ty)
+}
+
+fn is_identifier(src: &str) -> bool {
+    match src.chars().next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            src.chars().all(|c| c.is_alphanumeric() || c == '_')
         },
-        "EGLImageKHR" => "types::EGLImageKHR",
-        "EGLImage" => "types::EGLImage",
-        "EGLOutputLayerEXT" => "types::EGLOutputLayerEXT",
-        "EGLOutputPortEXT" => "types::EGLOutputPortEXT",
-        "EGLSyncKHR" => "types::EGLSyncKHR",
-        "EGLSync" => "types::EGLSync",
-        "EGLTimeKHR" => "types::EGLTimeKHR",
-        "EGLTime" => "types::EGLTime",
-        "EGLSyncNV" => "types::EGLSyncNV",
-        "EGLTimeNV" => "types::EGLTimeNV",
-        "EGLuint64NV" => "types::EGLuint64NV",
-        "EGLStreamKHR" => "types::EGLStreamKHR",
-        "EGLuint64KHR" => "types::EGLuint64KHR",
-        "EGLNativeFileDescriptorKHR" => "types::EGLNativeFileDescriptorKHR",
-        "EGLsizeiANDROID" => "types::EGLsizeiANDROID",
-        "EGLSetBlobFuncANDROID" => "types::EGLSetBlobFuncANDROID",
-        "EGLGetBlobFuncANDROID" => "types::EGLGetBlobFuncANDROID",
-        "EGLClientPixmapHI" => "types::EGLClientPixmapHI",
-        "struct EGLClientPixmapHI *" => "*const types::EGLClientPixmapHI",
-        "const EGLAttribKHR *" => "*const types::EGLAttribKHR",
-        "const EGLuint64KHR *" => "*const types::EGLuint64KHR",
-        "EGLAttribKHR *" => "*mut types::EGLAttribKHR",
-        "EGLDeviceEXT *" => "*mut types::EGLDeviceEXT",
-        "EGLNativeDisplayType *" => "*mut types::EGLNativeDisplayType",
-        "EGLNativePixmapType *" => "*mut types::EGLNativePixmapType",
-        "EGLNativeWindowType *" => "*mut types::EGLNativeWindowType",
-        "EGLOutputLayerEXT *" => "*mut types::EGLOutputLayerEXT",
-        "EGLTimeKHR *" => "*mut types::EGLTimeKHR",
-        "EGLOutputPortEXT *" => "*mut types::EGLOutputPortEXT",
-        "EGLuint64KHR *" => "*mut types::EGLuint64KHR",
-        "const struct AHardwareBuffer *" => "*const __gl_imports::raw::c_void", // humm
-        "char *" => "*const __gl_imports::raw::c_char",
-        "struct wl_buffer *" => "*const __gl_imports::raw::c_void",
-        "struct wl_display *" => "*const __gl_imports::raw::c_void",
-        "struct wl_resource *" => "*const __gl_imports::raw::c_void",
-
-        "GLeglClientBufferEXT" => "types::GLeglClientBufferEXT",
-        "GLVULKANPROCNV" => "types::GLVULKANPROCNV",
-        "EGLDEBUGPROCKHR" => "types::EGLDEBUGPROCKHR",
-        "EGLObjectKHR" => "types::EGLObjectKHR",
-        "EGLLabelKHR" => "types::EGLLabelKHR",
-        "EGLnsecsANDROID" => "types::EGLnsecsANDROID",
-        "EGLnsecsANDROID *" => "*mut types::EGLnsecsANDROID",
-        "EGLBoolean *" => "*mut types::EGLBoolean",
-
-        // failure
-        _ => panic!("Type conversion not implemented for `{}`", ty.as_ref()),
+        _ => false,
+    }
+}
+
+/// Translates a C type like `const GLchar *const*` to Rust.
+///
+/// Registry types are prefixed with `types`, C primitives with `raw`. Only the innermost
+/// pointer of a non-`const`, non-struct type becomes `*mut`, unless `const_pointers` is set,
+/// in which case no pointer does.
+fn translate_c_type(ty: &str, types: &str, raw: &str, const_pointers: bool) -> Option<String> {
+    let ty = ty.trim();
+
+    // arrays are passed as pointers
+    if ty.ends_with(']') {
+        let open = ty.rfind('[')?;
+        let len = ty[open + 1..ty.len() - 1].trim();
+        if len.is_empty() || !len.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let elem = translate_c_type(&ty[..open], types, raw, const_pointers)?;
+        return Some(format!("*mut [{}; {}]", elem, len));
+    }
+
+    let ty = ty.replace('*', " * ");
+    let mut tokens = ty.split_whitespace();
+    let mut is_const = false;
+    let mut is_struct = false;
+    let mut base = Vec::new();
+    let mut pointers = 0;
+    for token in tokens.by_ref() {
+        match token {
+            "const" => is_const = true,
+            "struct" => is_struct = true,
+            "*" => {
+                pointers = 1;
+                break;
+            },
+            token => base.push(token),
+        }
+    }
+    for token in tokens {
+        match token {
+            "*" => pointers += 1,
+            "const" => {},
+            _ => return None,
+        }
+    }
+
+    let base = base.join(" ");
+    let mut rust_ty = match (&*base, c_primitive(&base)) {
+        ("void", _) if pointers == 0 => return Some("()".to_string()),
+        ("void", _) => format!("{}c_void", raw),
+        (_, Some(prim)) if prim.starts_with("c_") => format!("{}{}", raw, prim),
+        (_, Some(prim)) => prim.to_string(),
+        (base, None) if is_identifier(base) => format!("{}{}", types, base),
+        _ => return None,
     };
+    for i in 0..pointers {
+        let mutable = i == 0 && !is_const && !is_struct && !const_pointers;
+        rust_ty = format!("{} {}", if mutable { "*mut" } else { "*const" }, rust_ty);
+    }
+    Some(rust_ty)
+}
 
-    Cow::Borrowed(ty)
+/// Translates a C type used inside the generated `types` module.
+fn to_rust_type_def(ty: &str, const_pointers: bool) -> Option<String> {
+    translate_c_type(ty, "", "super::__gl_imports::raw::", const_pointers)
+}
+
+/// Splits a C declaration like `char pipeName[80]` into its Rust type and identifier.
+fn split_declaration(decl: &str) -> Option<Binding> {
+    let decl = decl.trim();
+    let (decl, len) = match decl.rfind('[') {
+        Some(open) if decl.ends_with(']') => (&decl[..open], Some(&decl[open + 1..decl.len() - 1])),
+        _ => (decl, None),
+    };
+    let decl = decl.trim_end();
+    let start = decl
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+    let (ty, ident) = (&decl[..start], &decl[start..]);
+    if !is_identifier(ident) || ty.trim().is_empty() {
+        return None;
+    }
+    let mut ty = to_rust_type_def(ty, false)?;
+    if let Some(len) = len {
+        let len = len.trim();
+        if len.is_empty() || !len.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        ty = format!("[{}; {}]", ty, len);
+    }
+    Some(Binding {
+        ident: underscore_keyword(ident.to_string()),
+        ty: Cow::Owned(ty),
+        group: None,
+        len: None,
+    })
+}
+
+/// Translates the fields between the braces of a C struct.
+fn make_struct_fields(body: &str) -> Option<Vec<Binding>> {
+    let mut fields = Vec::new();
+    for decl in body
+        .split(';')
+        .map(str::trim)
+        .filter(|decl| !decl.is_empty())
+    {
+        // `int x, y` declares two fields of the same type
+        let mut declarators = decl.split(',');
+        let first = declarators.next()?;
+        let base = first
+            .trim()
+            .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_' || c == ']' || c == '[')
+            .trim_end_matches(|c: char| c == '*' || c.is_whitespace());
+        fields.push(split_declaration(first)?);
+        for declarator in declarators {
+            fields.push(split_declaration(&format!("{} {}", base, declarator))?);
+        }
+    }
+    Some(fields)
+}
+
+/// Translates the parameters of a C function pointer.
+fn make_fn_params(params: &str) -> Option<Vec<Binding>> {
+    match params.trim() {
+        "" | "void" => Some(Vec::new()),
+        params => params.split(',').map(split_declaration).collect(),
+    }
+}
+
+/// Translates the C source around the `<name>` of a `<type>`.
+///
+/// Returns `None` for anything that has no obvious Rust counterpart, like unions.
+fn make_type_def(before: &str, after: &str, apientry: bool) -> Option<(TypeDef, Option<String>)> {
+    // struct AHardwareBuffer;
+    if before == "struct" && after == ";" {
+        return Some((TypeDef::Opaque, None));
+    }
+
+    // struct _GPU_DEVICE { ... };
+    if before == "struct" && after.starts_with('{') && after.ends_with("};") {
+        let fields = make_struct_fields(&after[1..after.len() - 2])?;
+        return Some((TypeDef::Struct(fields), None));
+    }
+
+    // DECLARE_HANDLE(HPBUFFERARB);
+    if before == "DECLARE_HANDLE(" && after == ");" {
+        let handle = to_rust_type_def("void *", true)?;
+        return Some((TypeDef::Alias(handle), None));
+    }
+
+    if !before.starts_with("typedef ") || !after.ends_with(';') {
+        return None;
+    }
+    let before = before["typedef ".len()..].trim();
+    let after = after[..after.len() - 1].trim();
+
+    // typedef void (APIENTRY *GLDEBUGPROC)(GLenum source, ...);
+    if before.ends_with('*') && before[..before.len() - 1].trim_end().ends_with('(') {
+        let ret = before[..before.len() - 1].trim_end();
+        let ret = to_rust_type_def(&ret[..ret.len() - 1], false)?;
+        if !after.starts_with(')') || !after.ends_with(')') {
+            return None;
+        }
+        let params = after[1..after.len() - 1].trim();
+        if !params.starts_with('(') {
+            return None;
+        }
+        let def = TypeDef::FnPtr {
+            abi: if apientry { "system" } else { "C" },
+            params: make_fn_params(&params[1..])?,
+            ret,
+        };
+        return Some((def, None));
+    }
+
+    // typedef struct { ... } GLXPbufferClobberEvent;
+    if before.starts_with("struct") && before.ends_with('}') && after.is_empty() {
+        let open = before.find('{')?;
+        let fields = make_struct_fields(&before[open + 1..before.len() - 1])?;
+        return Some((TypeDef::Struct(fields), None));
+    }
+
+    if !after.is_empty() || before.contains('{') {
+        return None;
+    }
+
+    // typedef struct __GLsync *GLsync;
+    let tag = before.strip_prefix("struct ").and_then(|tag| {
+        tag.trim_end_matches(|c: char| c == '*' || c.is_whitespace())
+            .split_whitespace()
+            .next()
+            .map(str::to_string)
+    });
+
+    // typedef void GLvoid;
+    let alias = match before {
+        "void" => to_rust_type_def("void *", false)?.replace("*mut ", ""),
+        ty => to_rust_type_def(ty, true)?,
+    };
+    Some((TypeDef::Alias(alias), tag))
+}
+
+/// Removes C comments, which appear in the struct definitions of glx.xml.
+fn strip_comments(src: &str) -> String {
+    let mut out = String::new();
+    let mut rest = src;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start..].find("*/") {
+            Some(end) => &rest[start + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Converts a C style type definition to the Rust equivalent
+pub fn to_rust_ty<T: AsRef<str>>(ty: T) -> Option<Cow<'static, str>> {
+    // Due to a typo in khronos_api/api_angle/scripts/egl_angle_ext.xml - see brendanzab/gl-rs#491
+    let ty = ty.as_ref().replace("EGLattrib", "EGLAttrib");
+    translate_c_type(&ty, "types::", "__gl_imports::raw::", false).map(Cow::Owned)
 }
 
 #[cfg(test)]
 mod tests {
+    mod make_len {
+        use registry::parse;
+        use registry::Len;
+
+        #[test]
+        fn test_fixed() {
+            assert_eq!(parse::make_len("4"), Len::Fixed(4));
+        }
+
+        #[test]
+        fn test_param() {
+            assert_eq!(parse::make_len("count"), Len::Param("count".to_string()));
+            assert_eq!(parse::make_len("type"), Len::Param("type_".to_string()));
+        }
+
+        #[test]
+        fn test_expr() {
+            assert_eq!(parse::make_len("count*4"), Len::Expr("count*4".to_string()));
+        }
+
+        #[test]
+        fn test_compsize() {
+            assert_eq!(parse::make_len("COMPSIZE()"), Len::CompSize(Vec::new()));
+            assert_eq!(
+                parse::make_len("COMPSIZE(format,type,width)"),
+                Len::CompSize(vec![
+                    "format".to_string(),
+                    "type_".to_string(),
+                    "width".to_string(),
+                ])
+            );
+        }
+    }
+
     mod underscore_numeric_prefix {
         use registry::parse;
 
@@ -1122,6 +1651,7 @@ mod tests {
 
         #[test]
         fn test_keyword() {
+            assert_eq!(parse::underscore_keyword("box".to_string()), "box_");
             assert_eq!(parse::underscore_keyword("in".to_string()), "in_");
             assert_eq!(parse::underscore_keyword("ref".to_string()), "ref_");
             assert_eq!(parse::underscore_keyword("type".to_string()), "type_");
@@ -1135,6 +1665,13 @@ mod tests {
     }
     mod make_enum {
         use registry::parse;
+        use registry::RegistryError;
+
+        #[test]
+        fn test_negative_value() {
+            let e = parse::make_enum("FOO".to_string(), None, "-2".to_string(), None).unwrap();
+            assert_eq!((&*e.ty, &*e.value), ("GLint", "-2"));
+        }
 
         #[test]
         fn test_cast_0() {
@@ -1143,7 +1680,8 @@ mod tests {
                 None,
                 "((EGLint)-1)".to_string(),
                 Some("BAR".to_string()),
-            );
+            )
+            .unwrap();
             assert_eq!(e.ident, "FOO");
             assert_eq!((&*e.ty, &*e.value), ("EGLint", "-1"));
             assert_eq!(e.alias, Some("BAR".to_string()));
@@ -1156,7 +1694,8 @@ mod tests {
                 None,
                 "((EGLint)(-1))".to_string(),
                 Some("BAR".to_string()),
-            );
+            )
+            .unwrap();
             assert_eq!(e.ident, "FOO");
             assert_eq!((&*e.ty, &*e.value), ("EGLint", "(-1)"));
             assert_eq!(e.alias, Some("BAR".to_string()));
@@ -1169,7 +1708,8 @@ mod tests {
                 None,
                 "value".to_string(),
                 Some("BAR".to_string()),
-            );
+            )
+            .unwrap();
             assert_eq!(e.ident, "FOO");
             assert_eq!(e.value, "value");
             assert_eq!(e.alias, Some("BAR".to_string()));
@@ -1184,7 +1724,8 @@ mod tests {
                 Some("u".to_string()),
                 String::new(),
                 None,
-            );
+            )
+            .unwrap();
             assert_eq!(e.ty, "GLuint");
         }
 
@@ -1195,42 +1736,51 @@ mod tests {
                 Some("ull".to_string()),
                 String::new(),
                 None,
-            );
+            )
+            .unwrap();
             assert_eq!(e.ty, "GLuint64");
         }
 
         #[test]
-        #[should_panic]
         fn test_unknown_type() {
-            parse::make_enum(
+            let e = parse::make_enum(
                 "FOO".to_string(),
                 Some("blargh".to_string()),
                 String::new(),
                 None,
             );
+            assert_eq!(
+                e,
+                Err(RegistryError::UnhandledEnumType {
+                    ident: "FOO".to_string(),
+                    ty: "blargh".to_string(),
+                    position: None,
+                })
+            );
         }
 
         #[test]
         fn test_value_str() {
-            let e = parse::make_enum("FOO".to_string(), None, "\"hi\"".to_string(), None);
+            let e = parse::make_enum("FOO".to_string(), None, "\"hi\"".to_string(), None).unwrap();
             assert_eq!(e.ty, "&'static str");
         }
 
         #[test]
         fn test_ident_true() {
-            let e = parse::make_enum("TRUE".to_string(), None, String::new(), None);
+            let e = parse::make_enum("TRUE".to_string(), None, String::new(), None).unwrap();
             assert_eq!(e.ty, "GLboolean");
         }
 
         #[test]
         fn test_ident_false() {
-            let e = parse::make_enum("FALSE".to_string(), None, String::new(), None);
+            let e = parse::make_enum("FALSE".to_string(), None, String::new(), None).unwrap();
             assert_eq!(e.ty, "GLboolean");
         }
     }
 
     mod make_egl_enum {
         use registry::parse;
+        use registry::RegistryError;
 
         #[test]
         fn test_cast_egl() {
@@ -1239,7 +1789,8 @@ mod tests {
                 None,
                 "EGL_CAST(EGLint,-1)".to_string(),
                 Some("BAR".to_string()),
-            );
+            )
+            .unwrap();
             assert_eq!(e.ident, "FOO");
             assert_eq!((&*e.ty, &*e.value), ("EGLint", "-1"));
             assert_eq!(e.alias, Some("BAR".to_string()));
@@ -1247,13 +1798,15 @@ mod tests {
 
         #[test]
         fn test_ident_true() {
-            let e = parse::make_egl_enum("TRUE".to_string(), None, "1234".to_string(), None);
+            let e =
+                parse::make_egl_enum("TRUE".to_string(), None, "1234".to_string(), None).unwrap();
             assert_eq!(e.ty, "EGLBoolean");
         }
 
         #[test]
         fn test_ident_false() {
-            let e = parse::make_egl_enum("FALSE".to_string(), None, "1234".to_string(), None);
+            let e =
+                parse::make_egl_enum("FALSE".to_string(), None, "1234".to_string(), None).unwrap();
             assert_eq!(e.ty, "EGLBoolean");
         }
 
@@ -1264,37 +1817,500 @@ mod tests {
                 Some("ull".to_string()),
                 "1234".to_string(),
                 None,
-            );
+            )
+            .unwrap();
             assert_eq!(e.ty, "EGLuint64KHR");
         }
 
         #[test]
         fn test_negative_value() {
-            let e = parse::make_egl_enum("FOO".to_string(), None, "-1".to_string(), None);
+            let e = parse::make_egl_enum("FOO".to_string(), None, "-1".to_string(), None).unwrap();
             assert_eq!(e.ty, "EGLint");
         }
 
         #[test]
-        #[should_panic]
         fn test_unknown_type() {
-            parse::make_egl_enum(
+            let e = parse::make_egl_enum(
                 "FOO".to_string(),
                 Some("blargh".to_string()),
-                String::new(),
+                "1234".to_string(),
                 None,
             );
+            assert_eq!(
+                e,
+                Err(RegistryError::UnhandledEnumType {
+                    ident: "FOO".to_string(),
+                    ty: "blargh".to_string(),
+                    position: None,
+                })
+            );
         }
 
         #[test]
-        #[should_panic]
         fn test_unknown_value() {
-            parse::make_egl_enum("FOO".to_string(), None, "a".to_string(), None);
+            let e = parse::make_egl_enum("FOO".to_string(), None, "a".to_string(), None);
+            assert_eq!(
+                e,
+                Err(RegistryError::MalformedEnumValue {
+                    ident: "FOO".to_string(),
+                    value: "a".to_string(),
+                    position: None,
+                })
+            );
         }
 
         #[test]
-        #[should_panic]
         fn test_empty_value() {
-            parse::make_egl_enum("FOO".to_string(), None, String::new(), None);
+            let e = parse::make_egl_enum("FOO".to_string(), None, String::new(), None);
+            assert!(e.is_err());
+        }
+    }
+
+    mod to_rust_ty {
+        use registry::parse;
+
+        fn to_rust_ty(ty: &str) -> Option<String> {
+            parse::to_rust_ty(ty).map(|ty| ty.into_owned())
+        }
+
+        #[test]
+        fn test_values() {
+            assert_eq!(to_rust_ty("void"), Some("()".to_string()));
+            assert_eq!(to_rust_ty("GLuint"), Some("types::GLuint".to_string()));
+            assert_eq!(
+                to_rust_ty("const GLfloat"),
+                Some("types::GLfloat".to_string())
+            );
+            assert_eq!(
+                to_rust_ty("unsigned long"),
+                Some("__gl_imports::raw::c_ulong".to_string())
+            );
+            assert_eq!(to_rust_ty("int64_t"), Some("i64".to_string()));
+        }
+
+        #[test]
+        fn test_pointers() {
+            assert_eq!(
+                to_rust_ty("void *"),
+                Some("*mut __gl_imports::raw::c_void".to_string())
+            );
+            assert_eq!(
+                to_rust_ty("const GLint*"),
+                Some("*const types::GLint".to_string())
+            );
+            assert_eq!(
+                to_rust_ty("GLvoid **"),
+                Some("*const *mut types::GLvoid".to_string())
+            );
+            assert_eq!(
+                to_rust_ty("const GLchar *const*"),
+                Some("*const *const types::GLchar".to_string())
+            );
+            assert_eq!(
+                to_rust_ty("struct _cl_context *"),
+                Some("*const types::_cl_context".to_string())
+            );
+        }
+
+        #[test]
+        fn test_arrays() {
+            assert_eq!(
+                to_rust_ty("GLuint [2]"),
+                Some("*mut [types::GLuint; 2]".to_string())
+            );
+            assert_eq!(to_rust_ty("GLuint [N]"), None);
+        }
+
+        #[test]
+        fn test_untranslatable() {
+            assert_eq!(to_rust_ty(""), None);
+            assert_eq!(to_rust_ty("GLuint &"), None);
+            assert_eq!(to_rust_ty("GLuint * GLuint"), None);
+        }
+    }
+
+    mod from_xml {
+        use registry::parse;
+        use registry::{
+            Binding, ExtensionSelector, Filter, Len, RegistryError, SourcePosition, TypeDef,
+            Version,
+        };
+        use std::borrow::Cow;
+        use {Api, Fallbacks, Profile};
+
+        const REGISTRY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<registry>
+    <enums namespace="GL">
+        <enum value="0x4000" name="GL_COLOR_BUFFER_BIT"/>
+        <enum value="0x8242" name="GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB"/>
+    </enums>
+    <commands namespace="GL">
+        <command>
+            <proto>void <name>glClear</name></proto>
+            <param><ptype>GLbitfield</ptype> <name>mask</name></param>
+        </command>
+    </commands>
+    <feature api="gl" name="GL_VERSION_1_0" number="1.0">
+        <require>
+            <enum name="GL_COLOR_BUFFER_BIT"/>
+            <command name="glClear"/>
+        </require>
+    </feature>
+    <extensions>
+        <extension name="GL_ARB_debug_output" supported="gl|glcore">
+            <require>
+                <enum name="GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB"/>
+            </require>
+        </extension>
+    </extensions>
+</registry>
+"#;
+
+        fn filter<V: Into<Version>>(api: Api, version: V, extensions: &[&str]) -> Filter {
+            Filter::new(api, version, Profile::Core, Fallbacks::All, extensions)
+        }
+
+        #[test]
+        fn test_parse() {
+            let filter = filter(Api::Gl, (1, 0), &["GL_ARB_debug_output"]);
+            let registry = parse::from_xml(REGISTRY.as_bytes(), &filter, true).unwrap();
+            assert_eq!(registry.cmds.len(), 1);
+            assert_eq!(registry.enums.len(), 2);
+        }
+
+        #[test]
+        fn test_len() {
+            let src = REGISTRY.replace("glClear", "glUniform1fv").replace(
+                "<param><ptype>GLbitfield</ptype> <name>mask</name></param>",
+                "<param><ptype>GLint</ptype> <name>location</name></param>
+                <param><ptype>GLsizei</ptype> <name>count</name></param>
+                <param len=\"count\">const <ptype>GLfloat</ptype> *<name>value</name></param>",
+            );
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            let registry = parse::from_xml(src.as_bytes(), &filter, true).unwrap();
+
+            let uniform = registry.cmds.iter().next().unwrap();
+            assert_eq!(uniform.params[0].len, None);
+            assert_eq!(uniform.params[1].len, None);
+            assert_eq!(uniform.params[2].ty, "*const types::GLfloat");
+            assert_eq!(uniform.params[2].len, Some(Len::Param("count".to_string())));
+        }
+
+        #[test]
+        fn test_provenance() {
+            let src = REGISTRY.replace(
+                "<enum name=\"GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB\"/>",
+                "<enum name=\"GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB\"/><command name=\"glClear\"/>",
+            );
+            let filter = filter(Api::Gl, (1, 0), &["GL_ARB_debug_output"]);
+            let registry = parse::from_xml(src.as_bytes(), &filter, true).unwrap();
+
+            let clear = registry.cmds.iter().next().unwrap();
+            assert_eq!(clear.introduced_in, Some((1, 0)));
+            assert_eq!(clear.extensions, vec!["GL_ARB_debug_output".to_string()]);
+
+            let enums: Vec<_> = registry.enums.iter().collect();
+            assert_eq!(enums[0].ident, "COLOR_BUFFER_BIT");
+            assert_eq!(enums[0].introduced_in, Some((1, 0)));
+            assert!(enums[0].extensions.is_empty());
+            assert_eq!(enums[1].ident, "DEBUG_OUTPUT_SYNCHRONOUS_ARB");
+            assert_eq!(enums[1].introduced_in, None);
+            assert_eq!(enums[1].extensions, vec!["GL_ARB_debug_output".to_string()]);
+        }
+
+        #[test]
+        fn test_removed_from_core() {
+            let src = REGISTRY.replace(
+                "<extensions>",
+                "<feature api=\"gl\" name=\"GL_VERSION_3_2\" number=\"3.2\">
+                    <remove profile=\"core\"><command name=\"glClear\"/></remove>
+                </feature>
+                <extensions>",
+            );
+
+            let core = Filter::new(Api::Gl, (3, 2), Profile::Core, Fallbacks::All, []);
+            let registry = parse::from_xml(src.as_bytes(), &core, true).unwrap();
+            assert!(registry.cmds.is_empty());
+
+            let compat = Filter::new(Api::Gl, (3, 2), Profile::Compatibility, Fallbacks::All, []);
+            let registry = parse::from_xml(src.as_bytes(), &compat, true).unwrap();
+            let clear = registry.cmds.iter().next().unwrap();
+            assert_eq!(clear.introduced_in, Some((1, 0)));
+            assert_eq!(clear.removed_in, Some((3, 2)));
+            assert!(registry.enums.iter().all(|e| e.removed_in.is_none()));
+
+            // a later `<require profile="core">` brings it back
+            let src = src.replace(
+                "<extensions>",
+                "<feature api=\"gl\" name=\"GL_VERSION_4_3\" number=\"4.3\">
+                    <require profile=\"core\"><command name=\"glClear\"/></require>
+                </feature>
+                <extensions>",
+            );
+            let compat = Filter::new(Api::Gl, (4, 3), Profile::Compatibility, Fallbacks::All, []);
+            let registry = parse::from_xml(src.as_bytes(), &compat, true).unwrap();
+            let clear = registry.cmds.iter().next().unwrap();
+            assert_eq!(clear.removed_in, None);
+        }
+
+        #[test]
+        fn test_selectors() {
+            let arb = filter(Api::Gl, (1, 0), &[])
+                .select(ExtensionSelector::Pattern("GL_ARB_*".to_string()));
+            let registry = parse::from_xml(REGISTRY.as_bytes(), &arb, true).unwrap();
+            assert_eq!(registry.enums.len(), 2);
+            assert!(registry.extensions.contains("GL_ARB_debug_output"));
+
+            // selectors skip extensions that the api doesn't support, rather than failing
+            let all = filter(Api::Gles2, (1, 0), &[]).select(ExtensionSelector::All);
+            let registry = parse::from_xml(REGISTRY.as_bytes(), &all, false).unwrap();
+            assert!(registry.enums.is_empty());
+            assert!(registry.extensions.is_empty());
+        }
+
+        #[test]
+        fn test_unknown_version() {
+            let filter = filter(Api::Gl, (4, 6), &[]);
+            assert_eq!(
+                parse::from_xml(REGISTRY.as_bytes(), &filter, true),
+                Err(RegistryError::UnknownVersion {
+                    api: Api::Gl,
+                    version: (4, 6),
+                })
+            );
+        }
+
+        #[test]
+        fn test_version_ordering() {
+            // "1.10" is newer than "1.9", even though it sorts before it as a string
+            let src = REGISTRY.replace(
+                "<extensions>",
+                "<feature api=\"gl\" name=\"GL_VERSION_1_10\" number=\"1.10\">
+                    <require><enum name=\"GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB\"/></require>
+                </feature>
+                <feature api=\"gl\" name=\"GL_VERSION_1_9\" number=\"1.9\"/>
+                <extensions>",
+            );
+
+            let older = filter(Api::Gl, (1, 9), &[]);
+            let registry = parse::from_xml(src.as_bytes(), &older, true).unwrap();
+            assert_eq!(registry.version, (1, 9));
+            assert_eq!(registry.enums.len(), 1);
+
+            let newer = filter(Api::Gl, (1, 10), &[]);
+            let registry = parse::from_xml(src.as_bytes(), &newer, true).unwrap();
+            assert_eq!(registry.enums.len(), 2);
+        }
+
+        #[test]
+        fn test_latest_version() {
+            let gl = filter(Api::Gl, Version::Latest, &[]);
+            let registry = parse::from_xml(REGISTRY.as_bytes(), &gl, true).unwrap();
+            assert_eq!(registry.version, (1, 0));
+            assert_eq!(registry.cmds.len(), 1);
+
+            let gles2 = filter(Api::Gles2, Version::Latest, &[]);
+            assert_eq!(
+                parse::from_xml(REGISTRY.as_bytes(), &gles2, true),
+                Err(RegistryError::NoVersions { api: Api::Gles2 })
+            );
+        }
+
+        #[test]
+        fn test_malformed_version() {
+            let src = REGISTRY.replace("number=\"1.0\"", "number=\"1\"");
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            match parse::from_xml(src.as_bytes(), &filter, true) {
+                Err(RegistryError::MalformedVersion { number, .. }) => assert_eq!(number, "1"),
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        #[test]
+        fn test_require_api_and_profile() {
+            let src = REGISTRY
+                .replace(
+                    "<require>\n                <enum name=\"GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB\"/>",
+                    "<require api=\"gles2\">\n                <enum name=\"GL_DEBUG_OUTPUT_SYNCHRONOUS_ARB\"/>",
+                )
+                .replace(
+                    "<require>\n            <enum name=\"GL_COLOR_BUFFER_BIT\"/>",
+                    "<require profile=\"compatibility\">\n            <enum name=\"GL_COLOR_BUFFER_BIT\"/>",
+                );
+
+            let core = Filter::new(
+                Api::Gl,
+                (1, 0),
+                Profile::Core,
+                Fallbacks::All,
+                ["GL_ARB_debug_output"],
+            );
+            let registry = parse::from_xml(src.as_bytes(), &core, true).unwrap();
+            assert!(registry.enums.is_empty());
+            assert!(registry.cmds.is_empty());
+
+            let compat = Filter::new(
+                Api::Gl,
+                (1, 0),
+                Profile::Compatibility,
+                Fallbacks::All,
+                ["GL_ARB_debug_output"],
+            );
+            let registry = parse::from_xml(src.as_bytes(), &compat, true).unwrap();
+            let enums: Vec<_> = registry.enums.iter().map(|e| &*e.ident).collect();
+            assert_eq!(enums, ["COLOR_BUFFER_BIT"]);
+            assert_eq!(registry.cmds.len(), 1);
+        }
+
+        #[test]
+        fn test_unsupported_extension() {
+            let filter = filter(Api::Gles2, (1, 0), &["GL_ARB_debug_output"]);
+            assert_eq!(
+                parse::from_xml(REGISTRY.as_bytes(), &filter, false),
+                Err(RegistryError::UnsupportedExtension {
+                    extension: "GL_ARB_debug_output".to_string(),
+                    api: Api::Gles2,
+                })
+            );
+        }
+
+        #[test]
+        fn test_unknown_api() {
+            let src = REGISTRY.replace("supported=\"gl|glcore\"", "supported=\"gl|glfoo\"");
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            match parse::from_xml(src.as_bytes(), &filter, true) {
+                Err(RegistryError::UnknownApi { name, position }) => {
+                    assert_eq!(name, "glfoo");
+                    assert_eq!(position.line, 20);
+                },
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        #[test]
+        fn test_unknown_type() {
+            let src = REGISTRY.replace(
+                "<ptype>GLbitfield</ptype>",
+                "<ptype>GLbitfield</ptype> &amp;",
+            );
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            match parse::from_xml(src.as_bytes(), &filter, true) {
+                Err(RegistryError::UnknownType { ty, position }) => {
+                    assert_eq!(ty, "GLbitfield &");
+                    assert_eq!(position.line, 10);
+                },
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        #[test]
+        fn test_types() {
+            let types = r#"<registry>
+    <types>
+        <type name="khrplatform">#include &lt;KHR/khrplatform.h&gt;</type>
+        <type name="Display"/>
+        <type name="int32_t" requires="inttypes"/>
+        <type>typedef unsigned int <name>GLbitfield</name>;</type>
+        <type>typedef float <name>GLfloat</name>;</type>
+        <type api="gles2" requires="khrplatform">typedef khronos_float_t <name>GLfloat</name>;</type>
+        <type api="gles1" requires="khrplatform">typedef khronos_int32_t <name>GLfloat</name>;</type>
+        <type>typedef struct __GLsync *<name>GLsync</name>;</type>
+        <type comment="compatible with OpenCL cl_event"><name>struct _cl_event</name>;</type>
+        <type>typedef void (<apientry/> *<name>GLPROC</name>)(GLenum type,const void *userParam);</type>
+        <type>typedef struct {
+    int type;                   /* event type */
+    Display *display;
+    int x, y;
+    char name[80];
+} <name>GLevent</name>;</type>
+        <type>typedef union __GLevent2 {
+    GLevent event;
+    long pad[24];
+} <name>GLevent2</name>;</type>
+    </types>"#;
+            let src = REGISTRY.replace("<registry>", types);
+            let registry =
+                parse::from_xml(src.as_bytes(), &filter(Api::Gles2, (1, 0), &[]), false).unwrap();
+
+            let def = |ident: &str| registry.types[ident].def.clone();
+            let field = |ident: &str, ty: &'static str| Binding {
+                ident: ident.to_string(),
+                ty: Cow::Borrowed(ty),
+                group: None,
+                len: None,
+            };
+            assert_eq!(
+                def("khrplatform"),
+                TypeDef::Source("#include <KHR/khrplatform.h>".to_string())
+            );
+            assert_eq!(def("Display"), TypeDef::External);
+            assert_eq!(def("int32_t"), TypeDef::Alias("i32".to_string()));
+            assert_eq!(
+                def("GLbitfield"),
+                TypeDef::Alias("super::__gl_imports::raw::c_uint".to_string())
+            );
+            assert_eq!(
+                def("GLfloat"),
+                TypeDef::Alias("super::__gl_imports::raw::c_float".to_string())
+            );
+            assert_eq!(def("GLsync"), TypeDef::Alias("*const __GLsync".to_string()));
+            assert_eq!(def("__GLsync"), TypeDef::Opaque);
+            assert_eq!(def("_cl_event"), TypeDef::Opaque);
+            assert_eq!(
+                registry.types["_cl_event"].comment,
+                Some("compatible with OpenCL cl_event".to_string())
+            );
+            assert_eq!(
+                def("GLPROC"),
+                TypeDef::FnPtr {
+                    abi: "system",
+                    params: vec![
+                        field("type_", "GLenum"),
+                        field("userParam", "*const super::__gl_imports::raw::c_void"),
+                    ],
+                    ret: "()".to_string(),
+                }
+            );
+            assert_eq!(
+                def("GLevent"),
+                TypeDef::Struct(vec![
+                    field("type_", "super::__gl_imports::raw::c_int"),
+                    field("display", "*mut Display"),
+                    field("x", "super::__gl_imports::raw::c_int"),
+                    field("y", "super::__gl_imports::raw::c_int"),
+                    field("name", "[super::__gl_imports::raw::c_char; 80]"),
+                ])
+            );
+            assert_eq!(def("GLevent2"), TypeDef::Opaque);
+        }
+
+        #[test]
+        fn test_malformed_xml() {
+            let src = REGISTRY.replace("</commands>", "</command>");
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            match parse::from_xml(src.as_bytes(), &filter, true) {
+                Err(RegistryError::Xml { position, .. }) => {
+                    assert_eq!(position.line, 12);
+                },
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
+        #[test]
+        fn test_missing_attribute() {
+            let src = REGISTRY.replace(" number=\"1.0\"", "");
+            let filter = filter(Api::Gl, (1, 0), &[]);
+            assert_eq!(
+                parse::from_xml(src.as_bytes(), &filter, true),
+                Err(RegistryError::MissingAttribute {
+                    element: "feature".to_string(),
+                    attribute: "number".to_string(),
+                    position: SourcePosition {
+                        line: 13,
+                        column: 5,
+                    },
+                })
+            );
         }
     }
 
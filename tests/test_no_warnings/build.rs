@@ -34,6 +34,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gl_table {{").unwrap();
+    gl_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_lazy {{").unwrap();
+    gl_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_thread_local {{").unwrap();
+    gl_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod gl_static {{").unwrap();
     gl_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -58,6 +76,333 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gl_hook_struct {{").unwrap();
+    gl_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_stats_struct {{").unwrap();
+    gl_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let profiled = StatsStructGenerator {
+        timing: true,
+        call_sites: true,
+    };
+    writeln!(&mut file, "mod gl_stats_struct_profiled {{").unwrap();
+    gl_registry.write_bindings(profiled, &mut file).unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_checked_struct {{").unwrap();
+    gl_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_trait {{").unwrap();
+    gl_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Gl compatibility profile, with removed items deprecated
+
+    let gl_compat_registry =
+        Registry::new(Api::Gl, (4, 6), Profile::Compatibility, Fallbacks::All, []);
+    let deprecate_removed = GeneratorOptions {
+        deprecate_removed: true,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_compat_global {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(GlobalGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_table {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(TableGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_lazy {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(LazyGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_thread_local {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(ThreadLocalGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_static {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(StaticGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(StructGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_static_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(StaticStructGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_debug_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(DebugStructGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_hook_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(HookStructGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_stats_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(
+            StatsStructGenerator::default(),
+            &deprecate_removed,
+            &mut file,
+        )
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_checked_struct {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(CheckedStructGenerator, &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_compat_trait {{").unwrap();
+    gl_compat_registry
+        .write_bindings_with_options(TraitGenerator::default(), &deprecate_removed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Gl, with typed enum groups, enum names, load reports and context info
+
+    let typed = GeneratorOptions {
+        typed_groups: true,
+        enum_names: true,
+        load_report: true,
+        context_info: true,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_typed_global {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_table {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(TableGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_lazy {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(LazyGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_thread_local {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(ThreadLocalGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_static {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StaticGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_static_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StaticStructGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_debug_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(DebugStructGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_hook_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(HookStructGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_stats_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StatsStructGenerator::default(), &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_checked_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(CheckedStructGenerator, &typed, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_typed_trait {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(
+            TraitGenerator {
+                default_bodies: DefaultBodies::NoOp,
+            },
+            &typed,
+            &mut file,
+        )
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Gl, with each way of handling missing functions
+
+    let missing_noop = GeneratorOptions {
+        missing_fn: MissingFn::NoOp,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_missing_noop_global {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &missing_noop, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_noop_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &missing_noop, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_noop_debug_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(DebugStructGenerator, &missing_noop, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_noop_checked_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(CheckedStructGenerator, &missing_noop, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let missing_error = GeneratorOptions {
+        missing_fn: MissingFn::Error,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_missing_error_global {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &missing_error, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_error_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &missing_error, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_error_debug_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(DebugStructGenerator, &missing_error, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_error_checked_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(CheckedStructGenerator, &missing_error, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let missing_abort = GeneratorOptions {
+        missing_fn: MissingFn::Abort,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_missing_abort_global {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &missing_abort, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_abort_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &missing_abort, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_abort_debug_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(DebugStructGenerator, &missing_abort, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_abort_checked_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(CheckedStructGenerator, &missing_abort, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    let missing_panic_at_caller = GeneratorOptions {
+        missing_fn: MissingFn::PanicAtCaller,
+        ..GeneratorOptions::default()
+    };
+
+    writeln!(&mut file, "mod gl_missing_panic_at_caller_global {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(GlobalGenerator, &missing_panic_at_caller, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_panic_at_caller_struct {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(StructGenerator, &missing_panic_at_caller, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gl_missing_panic_at_caller_table {{").unwrap();
+    gl_registry
+        .write_bindings_with_options(TableGenerator, &missing_panic_at_caller, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(
+        &mut file,
+        "mod gl_missing_panic_at_caller_checked_struct {{"
+    )
+    .unwrap();
+    gl_registry
+        .write_bindings_with_options(CheckedStructGenerator, &missing_panic_at_caller, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Glx
 
     let glx_registry = Registry::new(Api::Glx, (1, 4), Profile::Core, Fallbacks::All, []);
@@ -68,6 +413,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod glx_table {{").unwrap();
+    glx_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_lazy {{").unwrap();
+    glx_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_thread_local {{").unwrap();
+    glx_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod glx_static {{").unwrap();
     glx_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -92,6 +455,48 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod glx_hook_struct {{").unwrap();
+    glx_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_stats_struct {{").unwrap();
+    glx_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_checked_struct {{").unwrap();
+    glx_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_trait {{").unwrap();
+    glx_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    // Glx with all extensions, which name types that no template declares
+
+    let glx_all_filter = Filter::new(Api::Glx, (1, 4), Profile::Core, Fallbacks::All, [])
+        .select(ExtensionSelector::All);
+    let glx_all_registry = Registry::from_filter(&glx_all_filter).unwrap();
+
+    writeln!(&mut file, "mod glx_all_global {{").unwrap();
+    glx_all_registry
+        .write_bindings(GlobalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod glx_all_struct {{").unwrap();
+    glx_all_registry
+        .write_bindings(StructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Wgl
 
     let wgl_registry = Registry::new(Api::Wgl, (1, 0), Profile::Core, Fallbacks::All, []);
@@ -102,6 +507,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod wgl_table {{").unwrap();
+    wgl_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod wgl_lazy {{").unwrap();
+    wgl_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod wgl_thread_local {{").unwrap();
+    wgl_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod wgl_static {{").unwrap();
     wgl_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -126,6 +549,30 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod wgl_hook_struct {{").unwrap();
+    wgl_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod wgl_stats_struct {{").unwrap();
+    wgl_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod wgl_checked_struct {{").unwrap();
+    wgl_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod wgl_trait {{").unwrap();
+    wgl_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Gles1
 
     let gles1_registry = Registry::new(Api::Gles1, (1, 1), Profile::Core, Fallbacks::All, []);
@@ -136,6 +583,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gles1_table {{").unwrap();
+    gles1_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles1_lazy {{").unwrap();
+    gles1_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles1_thread_local {{").unwrap();
+    gles1_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod gles1_static {{").unwrap();
     gles1_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -160,6 +625,30 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gles1_hook_struct {{").unwrap();
+    gles1_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles1_stats_struct {{").unwrap();
+    gles1_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles1_checked_struct {{").unwrap();
+    gles1_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles1_trait {{").unwrap();
+    gles1_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Gles2
 
     let gles2_registry = Registry::new(Api::Gles2, (3, 1), Profile::Core, Fallbacks::All, []);
@@ -170,6 +659,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gles2_table {{").unwrap();
+    gles2_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles2_lazy {{").unwrap();
+    gles2_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles2_thread_local {{").unwrap();
+    gles2_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod gles2_static {{").unwrap();
     gles2_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -194,6 +701,30 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod gles2_hook_struct {{").unwrap();
+    gles2_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles2_stats_struct {{").unwrap();
+    gles2_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles2_checked_struct {{").unwrap();
+    gles2_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod gles2_trait {{").unwrap();
+    gles2_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     // Egl
 
     let egl_registry = Registry::new(Api::Egl, (1, 5), Profile::Core, Fallbacks::All, []);
@@ -204,6 +735,24 @@ fn main() {
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
 
+    writeln!(&mut file, "mod egl_table {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(TableGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod egl_lazy {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod egl_thread_local {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
     writeln!(&mut file, "mod egl_static {{ {}", build_egl_symbols()).unwrap();
     egl_registry
         .write_bindings(StaticGenerator, &mut file)
@@ -232,6 +781,35 @@ fn main() {
         .write_bindings(DebugStructGenerator, &mut file)
         .unwrap();
     writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod egl_hook_struct {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod egl_stats_struct {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(StatsStructGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(
+        &mut file,
+        "mod egl_checked_struct {{ {}",
+        build_egl_symbols()
+    )
+    .unwrap();
+    egl_registry
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
+
+    writeln!(&mut file, "mod egl_trait {{ {}", build_egl_symbols()).unwrap();
+    egl_registry
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+    writeln!(&mut file, "}}").unwrap();
 }
 
 fn build_egl_symbols() -> &'static str {
@@ -26,4 +26,106 @@ fn main() {
     Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
         .write_bindings(GlobalGenerator, &mut file)
         .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_typed.rs")).unwrap();
+    let options = GeneratorOptions {
+        typed_groups: true,
+        enum_names: true,
+        load_report: true,
+        ..GeneratorOptions::default()
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_debug.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(DebugStructGenerator, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_checked.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Compatibility, Fallbacks::All, [])
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_checked_core.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(CheckedStructGenerator, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_trait.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(TraitGenerator::default(), &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_table.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(TableGenerator, &options, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_lazy.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(LazyGenerator, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_thread_local.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(ThreadLocalGenerator, &mut file)
+        .unwrap();
+
+    let mut file =
+        File::create(&Path::new(&dest).join("test_symbols_thread_local_noop.rs")).unwrap();
+    let options = GeneratorOptions {
+        missing_fn: MissingFn::NoOp,
+        ..GeneratorOptions::default()
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(ThreadLocalGenerator, &options, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_missing.rs")).unwrap();
+    let options = GeneratorOptions {
+        missing_fn: MissingFn::Error,
+        ..GeneratorOptions::default()
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
+
+    let mut file =
+        File::create(&Path::new(&dest).join("test_symbols_missing_at_caller.rs")).unwrap();
+    let options = GeneratorOptions {
+        missing_fn: MissingFn::PanicAtCaller,
+        ..GeneratorOptions::default()
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings_with_options(GlobalGenerator, &options, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_hook.rs")).unwrap();
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(HookStructGenerator, &mut file)
+        .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_symbols_stats.rs")).unwrap();
+    let generator = StatsStructGenerator {
+        timing: true,
+        call_sites: true,
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, [])
+        .write_bindings(generator, &mut file)
+        .unwrap();
 }
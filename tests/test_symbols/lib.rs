@@ -18,6 +18,64 @@ pub mod gl {
     include!(concat!(env!("OUT_DIR"), "/test_symbols.rs"));
 }
 
+pub mod gl_typed {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_typed.rs"));
+}
+
+pub mod gl_debug {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_debug.rs"));
+}
+
+pub mod gl_checked {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_checked.rs"));
+}
+
+pub mod gl_checked_core {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_checked_core.rs"));
+}
+
+pub mod gl_trait {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_trait.rs"));
+}
+
+pub mod gl_table {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_table.rs"));
+}
+
+pub mod gl_lazy {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_lazy.rs"));
+}
+
+pub mod gl_thread_local {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_thread_local.rs"));
+}
+
+pub mod gl_thread_local_noop {
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/test_symbols_thread_local_noop.rs"
+    ));
+}
+
+pub mod gl_missing {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_missing.rs"));
+}
+
+pub mod gl_missing_at_caller {
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/test_symbols_missing_at_caller.rs"
+    ));
+}
+
+pub mod gl_hook {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_hook.rs"));
+}
+
+pub mod gl_stats {
+    include!(concat!(env!("OUT_DIR"), "/test_symbols_stats.rs"));
+}
+
 pub fn compile_test_symbols_exist() {
     unsafe {
         gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -32,6 +90,263 @@ pub fn compile_test_symbols_exist() {
     }
 }
 
+pub fn compile_test_typed_symbols_exist() {
+    use gl_typed::groups::{BufferAccessMask, BufferTargetARB, ClearBufferMask, TextureTarget};
+
+    unsafe {
+        gl_typed::Clear(ClearBufferMask::COLOR_BUFFER_BIT | ClearBufferMask::DEPTH_BUFFER_BIT);
+        gl_typed::MapBufferRange(
+            BufferTargetARB::ARRAY_BUFFER,
+            0,
+            16,
+            BufferAccessMask::MAP_READ_BIT,
+        );
+        gl_typed::BindBuffer(BufferTargetARB::ARRAY_BUFFER, 0);
+        gl_typed::BindTexture(TextureTarget::TEXTURE_2D, 0);
+        gl_typed::BindTexture(TextureTarget(gl_typed::TEXTURE_3D), 0);
+    }
+}
+
+#[test]
+fn test_typed_group_debug() {
+    use gl_typed::groups::TextureTarget;
+
+    assert_eq!(TextureTarget::TEXTURE_2D.0, gl_typed::TEXTURE_2D);
+    assert_eq!(format!("{:?}", TextureTarget::TEXTURE_2D), "TEXTURE_2D");
+    assert_eq!(format!("{:?}", TextureTarget(0x1)), "TextureTarget(0x1)");
+}
+
+#[test]
+fn test_typed_bitmask_group() {
+    use gl_typed::groups::ClearBufferMask;
+
+    let mask = ClearBufferMask::COLOR_BUFFER_BIT | ClearBufferMask::STENCIL_BUFFER_BIT;
+    assert_eq!(
+        mask.bits(),
+        gl_typed::COLOR_BUFFER_BIT | gl_typed::STENCIL_BUFFER_BIT
+    );
+    assert!(mask.contains(ClearBufferMask::STENCIL_BUFFER_BIT));
+    assert!(!mask.contains(ClearBufferMask::DEPTH_BUFFER_BIT));
+    assert!((mask & ClearBufferMask::DEPTH_BUFFER_BIT).is_empty());
+    assert_eq!(
+        format!("{:?}", mask),
+        "COLOR_BUFFER_BIT | STENCIL_BUFFER_BIT"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            ClearBufferMask::DEPTH_BUFFER_BIT | ClearBufferMask(0x1)
+        ),
+        "DEPTH_BUFFER_BIT | 0x1"
+    );
+    assert_eq!(format!("{:?}", ClearBufferMask::empty()), "0x0");
+}
+
+#[test]
+fn test_enum_name() {
+    assert_eq!(gl_typed::enum_name(0x0502), Some("INVALID_OPERATION"));
+    assert_eq!(
+        gl_typed::enum_name(gl_typed::DRAW_FRAMEBUFFER),
+        Some("DRAW_FRAMEBUFFER")
+    );
+    assert_eq!(gl_typed::enum_name(0xDEAD), None);
+    assert_eq!(gl_typed::enum_name_in("ErrorCode", 0), Some("NO_ERROR"));
+    assert_eq!(
+        gl_typed::enum_name_in("ClearBufferMask", gl_typed::COLOR_BUFFER_BIT),
+        Some("COLOR_BUFFER_BIT")
+    );
+    assert_eq!(
+        gl_typed::enum_name_in("ErrorCode", gl_typed::TEXTURE_2D),
+        None
+    );
+}
+
+#[test]
+fn test_debug_sink() {
+    use std::sync::{Arc, Mutex};
+
+    extern "system" fn bind_buffer(_: gl_debug::types::GLenum, _: gl_debug::types::GLuint) {}
+    extern "system" fn get_error() -> gl_debug::types::GLenum {
+        gl_debug::INVALID_ENUM
+    }
+
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let sink_lines = lines.clone();
+    let gl = gl_debug::Gl::load_with_sink(
+        |name| match name {
+            "glBindBuffer" => bind_buffer as *const raw::c_void,
+            "glGetError" => get_error as *const raw::c_void,
+            _ => std::ptr::null(),
+        },
+        move |line| sink_lines.lock().unwrap().push(line.to_string()),
+    );
+
+    unsafe { gl.BindBuffer(gl_debug::ARRAY_BUFFER, 3) };
+
+    assert_eq!(
+        *lines.lock().unwrap(),
+        [
+            "BindBuffer(ARRAY_BUFFER, 3)",
+            "^ GL error triggered: INVALID_ENUM"
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "gl::GetError was not loaded")]
+fn test_debug_missing_get_error_panics() {
+    extern "system" fn bind_buffer(_: gl_debug::types::GLenum, _: gl_debug::types::GLuint) {}
+
+    let gl = gl_debug::Gl::load_with_sink(
+        |name| match name {
+            "glBindBuffer" => bind_buffer as *const raw::c_void,
+            _ => std::ptr::null(),
+        },
+        |_| {},
+    );
+    unsafe { gl.BindBuffer(gl_debug::ARRAY_BUFFER, 3) };
+}
+
+#[test]
+fn test_checked_errors() {
+    use gl_checked::types::{GLenum, GLuint};
+    use gl_checked::GlError;
+    use std::cell::Cell;
+
+    thread_local!(static ERROR: Cell<GLenum> = Cell::new(gl_checked::NO_ERROR));
+
+    extern "system" fn bind_buffer(_: GLenum, _: GLuint) {
+        ERROR.with(|e| e.set(gl_checked::INVALID_ENUM));
+    }
+    extern "system" fn vertex2f(_: f32, _: f32) {
+        ERROR.with(|e| e.set(gl_checked::INVALID_OPERATION));
+    }
+    extern "system" fn get_error() -> GLenum {
+        ERROR.with(|e| e.replace(gl_checked::NO_ERROR))
+    }
+    extern "system" fn clear(_: GLuint) {}
+    extern "system" fn vertex_attrib2f(_: GLuint, _: f32, _: f32) {
+        ERROR.with(|e| e.set(gl_checked::INVALID_VALUE));
+    }
+
+    let gl = gl_checked::Gl::load_with(|name| match name {
+        "glBindBuffer" => bind_buffer as *const raw::c_void,
+        "glVertex2f" => vertex2f as *const raw::c_void,
+        "glGetError" => get_error as *const raw::c_void,
+        "glClear" => clear as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        assert_eq!(
+            gl.BindBuffer(gl_checked::ARRAY_BUFFER, 0),
+            Err(GlError::InvalidEnum)
+        );
+        assert_eq!(gl.Clear(gl_checked::COLOR_BUFFER_BIT), Ok(()));
+        // not checked, as it may be called between `Begin` and `End`
+        assert_eq!(gl.Vertex2f(0.0, 0.0), Ok(()));
+        assert_eq!(
+            gl.Clear(gl_checked::COLOR_BUFFER_BIT),
+            Err(GlError::InvalidOperation)
+        );
+    }
+
+    // without `Begin`, the commands that may be called between `Begin` and `End` are checked
+    let gl = gl_checked_core::Gl::load_with(|name| match name {
+        "glVertexAttrib2f" => vertex_attrib2f as *const raw::c_void,
+        "glGetError" => get_error as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    unsafe {
+        assert_eq!(
+            gl.VertexAttrib2f(0, 0.0, 0.0),
+            Err(gl_checked_core::GlError::InvalidValue)
+        );
+    }
+
+    assert_eq!(GlError::from_code(0x0505), GlError::OutOfMemory);
+    assert_eq!(GlError::OutOfMemory.code(), gl_checked::OUT_OF_MEMORY);
+    assert_eq!(GlError::from_code(0xDEAD), GlError::Unknown(0xDEAD));
+    assert_eq!(GlError::InvalidValue.to_string(), "INVALID_VALUE");
+}
+
+#[test]
+fn test_trait_fake() {
+    use gl_trait::types::{GLbitfield, GLenum, GLuint};
+    use gl_trait::GlApi;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct RecordingGl {
+        calls: RefCell<Vec<String>>,
+    }
+
+    #[allow(non_snake_case)]
+    impl GlApi for RecordingGl {
+        unsafe fn BindBuffer(&self, target: GLenum, buffer: GLuint) {
+            let call = format!("BindBuffer({:#x}, {})", target, buffer);
+            self.calls.borrow_mut().push(call);
+        }
+        unsafe fn Clear(&self, mask: GLbitfield) {
+            self.calls.borrow_mut().push(format!("Clear({:#x})", mask));
+        }
+    }
+
+    fn render(gl: &dyn GlApi) {
+        unsafe {
+            gl.Clear(gl_trait::COLOR_BUFFER_BIT);
+            gl.BindBuffer(gl_trait::ARRAY_BUFFER, 1);
+        }
+    }
+
+    let gl = RecordingGl::default();
+    render(&gl);
+    assert_eq!(
+        *gl.calls.borrow(),
+        ["Clear(0x4000)", "BindBuffer(0x8892, 1)"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "DrawArrays is not implemented")]
+fn test_trait_default_panics() {
+    use gl_trait::GlApi;
+
+    struct FakeGl;
+    impl GlApi for FakeGl {}
+
+    unsafe { FakeGl.DrawArrays(gl_trait::TRIANGLES, 0, 3) };
+}
+
+#[test]
+fn test_load_report() {
+    use gl_typed::{Availability, Feature};
+
+    let report = gl_typed::load_with_report(|name| match name {
+        "glClear" | "glGenFramebuffersEXT" => 42 as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    let missing = report.missing();
+    assert!(missing.contains(&"glDrawArrays"));
+    assert!(!missing.contains(&"glClear"));
+    assert!(!missing.contains(&"glGenFramebuffers"));
+    assert_eq!(
+        report.fallbacks(),
+        [("glGenFramebuffers", "glGenFramebuffersEXT")]
+    );
+    assert_eq!(
+        report.availability(Feature::Version(1, 0)),
+        Some(Availability::Partial)
+    );
+    assert_eq!(
+        report.availability(Feature::Version(4, 6)),
+        Some(Availability::Unavailable)
+    );
+    assert_eq!(report.availability(Feature::Version(9, 9)), None);
+}
+
 #[test]
 fn test_fallback_works() {
     fn loader(name: &str) -> *const raw::c_void {
@@ -45,3 +360,268 @@ fn test_fallback_works() {
     gl::GenFramebuffers::load_with(loader);
     assert!(gl::GenFramebuffers::is_loaded());
 }
+
+#[test]
+fn test_load_from_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                gl::Flush::load_with(|name| match name {
+                    "glFlush" => 42 as *const raw::c_void,
+                    name => panic!("test tried to load {} unexpectedly!", name),
+                });
+                assert!(gl::Flush::is_loaded());
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert!(gl::Flush::is_loaded());
+}
+
+#[test]
+fn test_table_load() {
+    extern "system" fn get_error() -> gl_table::types::GLenum {
+        gl_table::INVALID_VALUE
+    }
+
+    let report = gl_table::load_with_report(|name| match name {
+        "glGetError" => get_error as *const raw::c_void,
+        "glGenFramebuffersEXT" => 42 as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    assert!(gl_table::is_loaded("glGetError"));
+    assert!(gl_table::is_loaded("glGenFramebuffers"));
+    assert!(!gl_table::is_loaded("glClear"));
+    assert!(!gl_table::is_loaded("glNotAFunction"));
+    assert_eq!(
+        report.fallbacks(),
+        [("glGenFramebuffers", "glGenFramebuffersEXT")]
+    );
+    assert_eq!(unsafe { gl_table::GetError() }, gl_table::INVALID_VALUE);
+}
+
+#[test]
+#[should_panic(expected = "gl::Flush was not loaded")]
+fn test_table_missing_fn_panics() {
+    unsafe { gl_table::Flush() };
+}
+
+#[test]
+fn test_lazy_load() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    extern "system" fn get_error() -> gl_lazy::types::GLenum {
+        gl_lazy::INVALID_VALUE
+    }
+
+    let loads = Arc::new(AtomicUsize::new(0));
+    let counter = loads.clone();
+    gl_lazy::load_with(move |name| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        match name {
+            "glGetError" => get_error as *const raw::c_void,
+            "glGenFramebuffersEXT" => 42 as *const raw::c_void,
+            _ => std::ptr::null(),
+        }
+    });
+    assert_eq!(loads.load(Ordering::SeqCst), 0);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| std::thread::spawn(|| unsafe { gl_lazy::GetError() }))
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), gl_lazy::INVALID_VALUE);
+    }
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+    gl_lazy::preload(&["glGenFramebuffers", "glNotAFunction"]);
+    assert_eq!(loads.load(Ordering::SeqCst), 3);
+    assert!(gl_lazy::is_loaded("glGenFramebuffers"));
+    assert!(!gl_lazy::is_loaded("glClear"));
+    assert_eq!(loads.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_thread_local_tables() {
+    use gl_thread_local::types::GLenum;
+    use gl_thread_local::GlContextTable;
+
+    extern "system" fn get_error_a() -> GLenum {
+        gl_thread_local::INVALID_ENUM
+    }
+    extern "system" fn get_error_b() -> GLenum {
+        gl_thread_local::INVALID_VALUE
+    }
+
+    let table_a = GlContextTable::load_with(|name| match name {
+        "glGetError" => get_error_a as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    let table_b = GlContextTable::load_with(|name| match name {
+        "glGetError" => get_error_b as *const raw::c_void,
+        "glGenFramebuffersEXT" => 42 as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    assert!(!table_a.is_loaded("glGenFramebuffers"));
+    assert!(table_b.is_loaded("glGenFramebuffers"));
+
+    gl_thread_local::make_current(&table_a);
+    assert_eq!(
+        unsafe { gl_thread_local::GetError() },
+        gl_thread_local::INVALID_ENUM
+    );
+
+    let thread = std::thread::spawn(move || {
+        gl_thread_local::make_current(&table_b);
+        unsafe { gl_thread_local::GetError() }
+    });
+    assert_eq!(thread.join().unwrap(), gl_thread_local::INVALID_VALUE);
+    assert_eq!(
+        unsafe { gl_thread_local::GetError() },
+        gl_thread_local::INVALID_ENUM
+    );
+
+    gl_thread_local::clear_current();
+    assert!(!gl_thread_local::is_loaded("glGetError"));
+}
+
+#[test]
+#[should_panic(expected = "gl::Flush was not loaded")]
+fn test_thread_local_no_current_table_panics() {
+    unsafe { gl_thread_local::Flush() };
+}
+
+#[test]
+fn test_thread_local_no_current_table_noop() {
+    assert!(!gl_thread_local_noop::is_loaded("glGetError"));
+    assert_eq!(unsafe { gl_thread_local_noop::GetError() }, 0);
+    assert!(unsafe {
+        gl_thread_local_noop::MapBuffer(
+            gl_thread_local_noop::ARRAY_BUFFER,
+            gl_thread_local_noop::READ_ONLY,
+        )
+    }
+    .is_null());
+}
+
+#[test]
+fn test_missing_fn_error() {
+    let gl = gl_missing::Gl::load_with(|_| std::ptr::null());
+
+    assert_eq!(gl_missing::take_missing_fn(), None);
+    assert_eq!(unsafe { gl.GetError() }, 0);
+    assert!(unsafe { gl.MapBuffer(gl_missing::ARRAY_BUFFER, gl_missing::READ_ONLY) }.is_null());
+    assert_eq!(gl_missing::take_missing_fn(), Some("MapBuffer"));
+    assert_eq!(gl_missing::take_missing_fn(), None);
+}
+
+#[test]
+#[should_panic(expected = "gl::Flush was not loaded")]
+fn test_missing_fn_panic() {
+    let gl = gl_checked::Gl::load_with(|_| std::ptr::null());
+    let _ = unsafe { gl.Flush() };
+}
+
+#[test]
+#[should_panic(expected = "gl::Flush was not loaded")]
+fn test_missing_fn_panic_at_caller() {
+    assert!(!gl_missing_at_caller::Flush::is_loaded());
+    unsafe { gl_missing_at_caller::Flush() };
+}
+
+#[test]
+fn test_hooks() {
+    use gl_hook::{CommandId, Hooks};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+
+    extern "system" fn bind_buffer(_: gl_hook::types::GLenum, _: gl_hook::types::GLuint) {}
+    extern "system" fn create_program() -> gl_hook::types::GLuint {
+        7
+    }
+
+    struct RecordingHooks(Arc<Mutex<Vec<String>>>);
+
+    impl Hooks for RecordingHooks {
+        fn before(&self, cmd: CommandId, args: &dyn Debug) {
+            let call = format!("before {}{:?}", cmd.name(), args);
+            self.0.lock().unwrap().push(call);
+        }
+        fn after(&self, cmd: CommandId, ret: &dyn Debug) {
+            let call = format!("after {:?} -> {:?}", cmd, ret);
+            self.0.lock().unwrap().push(call);
+        }
+    }
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let gl = gl_hook::Gl::load_with_hooks(
+        |name| match name {
+            "glBindBuffer" => bind_buffer as *const raw::c_void,
+            "glCreateProgram" => create_program as *const raw::c_void,
+            _ => std::ptr::null(),
+        },
+        RecordingHooks(calls.clone()),
+    );
+
+    unsafe {
+        gl.BindBuffer(gl_hook::ARRAY_BUFFER, 3);
+        assert_eq!(gl.CreateProgram(), 7);
+    }
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        [
+            "before BindBuffer(34962, 3)",
+            "after BindBuffer -> ()",
+            "before CreateProgram()",
+            "after CreateProgram -> 7",
+        ]
+    );
+    assert_eq!(CommandId::BindBuffer.introduced_in(), Some((1, 5)));
+    assert_eq!(CommandId::DrawArrays.name(), "DrawArrays");
+}
+
+#[test]
+fn test_stats() {
+    use gl_stats::{CommandId, SortBy};
+
+    extern "system" fn clear(_: gl_stats::types::GLbitfield) {}
+    extern "system" fn create_program() -> gl_stats::types::GLuint {
+        7
+    }
+
+    let gl = gl_stats::Gl::load_with(|name| match name {
+        "glClear" => clear as *const raw::c_void,
+        "glCreateProgram" => create_program as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+
+    for _ in 0..3 {
+        unsafe { gl.Clear(gl_stats::COLOR_BUFFER_BIT) };
+    }
+    assert_eq!(unsafe { gl.CreateProgram() }, 7);
+
+    let stats = gl.stats().take();
+    assert_eq!(stats.total_count(), 4);
+    assert_eq!(stats.get(CommandId::Clear).unwrap().count, 3);
+    assert_eq!(stats.get(CommandId::Flush), None);
+    let by_count = stats.sorted(SortBy::Count);
+    assert_eq!(by_count[0].cmd, CommandId::Clear);
+    assert_eq!(by_count[1].cmd, CommandId::CreateProgram);
+
+    assert_eq!(stats.call_sites.len(), 2);
+    assert_eq!(stats.call_sites[0].cmd, CommandId::Clear);
+    assert_eq!(stats.call_sites[0].count, 3);
+    assert_eq!(stats.call_sites[0].location.file(), file!());
+
+    let report = stats.report(SortBy::Time);
+    assert!(report.contains("Clear"));
+    assert!(report.contains("CreateProgram"));
+
+    assert_eq!(gl.stats().snapshot(), Default::default());
+}
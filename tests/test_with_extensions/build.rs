@@ -27,4 +27,14 @@ fn main() {
     Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, extensions)
         .write_bindings(GlobalGenerator, &mut file)
         .unwrap();
+
+    let mut file = File::create(&Path::new(&dest).join("test_context_info.rs")).unwrap();
+    let options = GeneratorOptions {
+        context_info: true,
+        ..GeneratorOptions::default()
+    };
+
+    Registry::new(Api::Gl, (4, 6), Profile::Core, Fallbacks::All, extensions)
+        .write_bindings_with_options(StructGenerator, &options, &mut file)
+        .unwrap();
 }
@@ -16,6 +16,10 @@ pub mod gl {
     include!(concat!(env!("OUT_DIR"), "/test_symbols.rs"));
 }
 
+pub mod gl_struct {
+    include!(concat!(env!("OUT_DIR"), "/test_context_info.rs"));
+}
+
 pub fn compile_test_symbols_exist() {
     let _ = gl::DebugMessageControlARB;
     let _ = gl::DebugMessageInsertARB;
@@ -45,3 +49,65 @@ pub fn compile_test_symbols_exist() {
     assert_eq!(gl::DEBUG_SEVERITY_MEDIUM_ARB, 0x9147);
     assert_eq!(gl::DEBUG_SEVERITY_LOW_ARB, 0x9148);
 }
+
+#[test]
+fn test_context_info_query() {
+    use gl_struct::types::{GLenum, GLint, GLubyte, GLuint};
+    use gl_struct::ContextInfo;
+    use std::os::raw;
+
+    extern "system" fn get_string(name: GLenum) -> *const GLubyte {
+        match name {
+            gl_struct::VERSION => b"4.6.0 NVIDIA 390.12\0".as_ptr(),
+            _ => std::ptr::null(),
+        }
+    }
+    extern "system" fn get_string_i(_: GLenum, index: GLuint) -> *const GLubyte {
+        match index {
+            0 => b"GL_ARB_debug_output\0".as_ptr(),
+            _ => b"GL_ARB_unknown\0".as_ptr(),
+        }
+    }
+    extern "system" fn get_integer_v(_: GLenum, data: *mut GLint) {
+        unsafe { *data = 2 };
+    }
+
+    let gl = gl_struct::Gl::load_with(|name| match name {
+        "glGetString" => get_string as *const raw::c_void,
+        "glGetStringi" => get_string_i as *const raw::c_void,
+        "glGetIntegerv" => get_integer_v as *const raw::c_void,
+        _ => std::ptr::null(),
+    });
+    let info = unsafe { ContextInfo::query(&gl) };
+
+    assert_eq!(info.version, (4, 6));
+    assert!(!info.is_es);
+    assert!(info.GL_ARB_debug_output);
+    assert!(info.supports_extension("GL_ARB_unknown"));
+    assert!(info.supports_version(4, 5));
+    assert!(!info.supports_version(4, 7));
+}
+
+#[test]
+fn test_context_info_versions() {
+    use gl_struct::ContextInfo;
+
+    assert_eq!(
+        ContextInfo::parse_version("3.3.0 Mesa 20.0.8"),
+        Some((false, (3, 3)))
+    );
+    assert_eq!(
+        ContextInfo::parse_version("OpenGL ES 3.2 Mesa 20.0.8"),
+        Some((true, (3, 2)))
+    );
+    assert_eq!(
+        ContextInfo::parse_version("OpenGL ES-CM 1.1"),
+        Some((true, (1, 1)))
+    );
+    assert_eq!(ContextInfo::parse_version("garbage"), None);
+
+    let info = ContextInfo::new("OpenGL ES 3.2", vec!["GL_ARB_debug_output"]);
+    assert!(info.GL_ARB_debug_output);
+    // the bindings are for desktop OpenGL
+    assert!(!info.supports_version(3, 0));
+}